@@ -17,6 +17,13 @@ su - $(logname) -c "cargo build --manifest-path $BASEDIR/../src/rust/Cargo.toml
 # FIXME: eventually this is where the service will be setup
 #        for now just run the bot
 mkdir /opt/et-mm-bot
-cp $BASEDIR/../runtime/config.cfg /opt/et-mm-bot/config.cfg
+# keep an installed configuration. a config.cfg from before the toml
+# configuration is converted by the bot the first time it starts
+if [ ! -f /opt/et-mm-bot/config.toml ] && [ ! -f /opt/et-mm-bot/config.cfg ]; then
+    cp $BASEDIR/../runtime/config.toml /opt/et-mm-bot/config.toml
+fi
 cp $BASEDIR/../src/rust/target/release/et-mm-bot /opt/et-mm-bot/et-mm-bot
 chown -R et_mm:et_mm /opt/et-mm-bot
+# the configuration may hold secrets, which can instead be given to the bot as
+# ETMM_ environment variables such as ETMM_DISCORD_TOKEN
+chmod 600 /opt/et-mm-bot/config.*
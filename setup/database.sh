@@ -40,23 +40,8 @@ printf "Restarting postgresql service..."
 systemctl restart postgresql
 printf "\t[OK]\n"
 
-# create database tables
-printf "Creating database tables...\n"
-for file in /tmp/database/tables/*; do
-    su - et_mm -s /bin/bash -c "psql -d et_mm -a -w -f $file"
-done
-
-# create internal database functions
-printf "Creating internal database functions...\n"
-for file in /tmp/database/functions/internal/*; do
-    su - et_mm -s /bin/bash -c "psql -d et_mm -a -w -f $file"
-done
-
-# create external database functions
-printf "Creating internal database functions...\n"
-for file in /tmp/database/functions/external/*; do
-    su - et_mm -s /bin/bash -c "psql -d et_mm -a -w -f $file"
-done
+# the bot creates the database tables and functions itself, applying any
+# pending schema migrations every time it starts or `et-mm-bot migrate` runs
 
 # cleanup
 printf "Cleaning up database scripts..."
@@ -0,0 +1,55 @@
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CheckResult,
+        CommandOptions,
+        macros::check
+    },
+    model::prelude::*,
+    prelude::*
+};
+use std::collections::HashSet;
+
+/// Admins structure for the discord roles whose members may use admin commands
+///
+/// # Members
+///
+///     ```
+///     roles: discord role ids of the admin roles
+///     ```
+pub struct Admins {
+    roles: HashSet<u64>
+}
+
+// Admins implementation
+impl Admins {
+    /// constructs the admin roles from the configured role ids.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let admins = admins::Admins::construct(&config.admin_roles());
+    /// ```
+    pub fn construct (roles: &[u64]) -> Self {
+        Self {
+            roles: roles.iter().cloned().collect()
+        }
+    }
+    /// returns whether any of the specified roles is an admin role.
+    pub fn is_admin (&self, roles: &[RoleId]) -> bool {
+        roles.iter().any(|r| self.roles.contains(r.as_u64()))
+    }
+}
+
+#[check]
+#[name = "Admin"]
+// only let members with one of the configured admin roles use a command. the
+// bot owner is let through by the framework without needing a role
+pub fn admin_check(context: &mut Context, message: &Message, _: &mut Args, _: &CommandOptions) -> CheckResult {
+    let admins = context.data.read().get::<Admins>().cloned().unwrap();
+    match &message.member {
+        Some(m) if admins.is_admin(&m.roles) => CheckResult::Success,
+        Some(_) => CheckResult::new_user("only admins can use this command."),
+        None => CheckResult::new_user("admin commands can only be used in a server channel.")
+    }
+}
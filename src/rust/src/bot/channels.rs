@@ -0,0 +1,101 @@
+use crate::match_making::queue::Queues;
+use serenity:: {
+    model::prelude::*,
+    prelude::*
+};
+use std:: {
+    collections::HashMap,
+    error::Error
+};
+
+/// Channels structure for the match making groups hosted in each configured
+/// channel
+///
+/// # Members
+///
+///     ```
+///     groups: match making groups keyed by discord channel id
+///     ```
+pub struct Channels {
+    groups: HashMap<u64, Vec<String>>
+}
+
+// Channels implementation
+impl Channels {
+    /// constructs the configured mapping from channels to match making groups.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let channels = channels::Channels::construct(config.channel_groups());
+    /// ```
+    pub fn construct (groups: HashMap<u64, Vec<String>>) -> Self {
+        Self {
+            groups
+        }
+    }
+}
+
+/// returns the match making groups hosted in a channel. configured channels
+/// host their configured groups, and any other channel hosts the group
+/// matching its name.
+///
+/// # Example
+///
+/// ```
+/// let groups = channels::groups(&context, message.channel_id).unwrap();
+/// ```
+pub fn groups (context: &Context, channel: ChannelId) -> Result<Vec<String>, Box<dyn Error>> {
+    let channels = context.data.read().get::<Channels>().cloned().unwrap();
+    if let Some(g) = channels.groups.get(channel.as_u64()) {
+        return Ok (g.clone());
+    }
+    match channel.name(context) {
+        Some(n) => Ok (vec![n.to_lowercase()]),
+        None => Err("error retrieving channel name.".into())
+    }
+}
+
+/// returns the match making group hosted in a channel, for commands that act
+/// on a single group when none is specified.
+///
+/// # Example
+///
+/// ```
+/// let group = channels::group(&context, message.channel_id).unwrap();
+/// ```
+pub fn group (context: &Context, channel: ChannelId) -> Result<String, Box<dyn Error>> {
+    let mut groups = groups(context, channel)?;
+    if groups.len() != 1 {
+        let names: Vec<String> = groups.iter().map(|g| format!("`{}`", g)).collect();
+        return Err(format!("this channel hosts several match making groups, specify one of: {}.", names.join(", ")).into());
+    }
+    Ok (groups.remove(0))
+}
+
+/// returns the match making groups requested in the arguments of a command,
+/// which are either group names or `all` for every group. the groups hosted
+/// in the channel are returned when none are requested.
+///
+/// # Example
+///
+/// ```
+/// let groups = channels::requested(&context, message.channel_id, args.rest()).unwrap();
+/// ```
+pub fn requested (context: &Context, channel: ChannelId, text: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut requested: Vec<String> = Vec::new();
+    for token in text.split_whitespace().map(|t| t.to_lowercase()) {
+        if token == "all" {
+            let queues = context.data.read().get::<Queues>().cloned().unwrap();
+            let groups = queues.lock().groups();
+            return Ok (groups);
+        }
+        if !requested.contains(&token) {
+            requested.push(token);
+        }
+    }
+    if requested.is_empty() {
+        return groups(context, channel);
+    }
+    Ok (requested)
+}
@@ -0,0 +1,68 @@
+use crate::bot::lobby;
+use crate::match_making:: {
+    matches::Matches,
+    penalties:: {
+        self,
+        Offence
+    }
+};
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// report the mentioned opponent for abandoning the match the user calling this
+// function is playing in. the opponent is banned from queueing once at least
+// half of their opponents have reported them
+pub fn abandon(context: &mut Context, message: &Message, mut args: Args) -> CommandResult {
+    let matches = context.data.read().get::<Matches>().cloned().unwrap();
+    let reply;
+    let player = match args.single::<UserId>() {
+        Ok (p) => *p.as_u64(),
+        Err(_) => {
+            reply = "usage: `!abandon @player`".to_string();
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let (match_id, abandoned) = {
+        let mut matches = matches.lock();
+        let game = match matches.find(*message.author.id.as_u64()) {
+            Some(m) => m,
+            None => {
+                reply = format!("`{}` is not playing in a match.", message.author.name);
+                let _ = message.channel_id.say(&context.http, &reply);
+                return Err(CommandError::from(reply));
+            }
+        };
+        match game.report_abandon(*message.author.id.as_u64(), player) {
+            Ok (a) => (game.id, a),
+            Err(e) => {
+                reply = format!("{}", e);
+                let _ = message.channel_id.say(&context.http, &reply);
+                return Err(CommandError::from(reply));
+            }
+        }
+    };
+    if !abandoned {
+        reply = format!("`{}` reported {} for abandoning match #{}.", message.author.name, lobby::mention(player), match_id);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Ok(());
+    }
+    reply = match lobby::penalize(&context.http, &context.data, player, Offence::AbandonedMatch) {
+        Some(ban) => format!(
+            "{} was reported for abandoning match #{} and cannot queue for {}.",
+            lobby::mention(player), match_id, penalties::format_duration(ban)
+        ),
+        None => format!("{} was reported for abandoning match #{} but could not be banned.", lobby::mention(player), match_id)
+    };
+    let _ = message.channel_id.say(&context.http, &reply);
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::bot::lobby;
+use crate::database::Database;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// adjust the rating of the mentioned player within a match making group by a
+// positive or negative amount: `!adjustrating @player <group> <amount>`
+pub fn adjustrating(context: &mut Context, message: &Message, mut args: Args) -> CommandResult {
+    let database = context.data.read().get::<Database>().cloned().unwrap();
+    let reply;
+    let (player, group, amount) = match (args.single::<UserId>(), args.single::<String>(), args.single::<f64>()) {
+        (Ok (p), Ok (g), Ok (a)) if a.is_finite() => (*p.as_u64(), g.to_lowercase(), a),
+        _ => {
+            reply = "usage: `!adjustrating @player <group> <amount>`".to_string();
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let result = match database.adjust_rating(player, &group, amount) {
+        Ok (r) => r,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    match result {
+        0 => {
+            let rating = database.get_mm_ratings(&group, &[player])
+                .ok()
+                .and_then(|r| r.first().map(|p| p.rating));
+            reply = match rating {
+                Some(r) => format!("the `{}` rating of {} has been adjusted by {:+.0} to {:.0}.", group, lobby::mention(player), amount, r),
+                None => format!("the `{}` rating of {} has been adjusted by {:+.0}.", group, lobby::mention(player), amount)
+            };
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Ok(());
+        },
+        1 => reply = format!("match making group: `{}` does not exist.", group),
+        2 => reply = format!("{} has never joined match making group: `{}`", lobby::mention(player), group),
+        _ => reply = format!("database returned an unkown result when calling `adjust_rating()`: `{}`", result)
+    };
+    let _ = message.channel_id.say(&context.http, &reply);
+    Err(CommandError::from(reply))
+}
@@ -0,0 +1,76 @@
+use crate::bot::lobby;
+use crate::database::Database;
+use crate::match_making:: {
+    penalties:: {
+        self,
+        Offence
+    },
+    queue::Queues
+};
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+use std::time::SystemTime;
+
+#[command]
+// ban the mentioned player from queueing for a number of minutes, hours or
+// days: `!ban @player 30m|2h|1d`. the player is removed from every queue they
+// are waiting in
+pub fn ban(context: &mut Context, message: &Message, mut args: Args) -> CommandResult {
+    let database = context.data.read().get::<Database>().cloned().unwrap();
+    let queues = context.data.read().get::<Queues>().cloned().unwrap();
+    let reply;
+    let (player, length) = match (args.single::<UserId>(), args.single::<String>()) {
+        (Ok (p), Ok (l)) => (*p.as_u64(), l),
+        _ => {
+            reply = "usage: `!ban @player 30m|2h|1d`".to_string();
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let length = match penalties::parse_duration(&length) {
+        Ok (l) => l,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    // parse_duration refuses lengths over MAX_BAN, so this only fails on a
+    // system clock set far into the future
+    let expires_at = match SystemTime::now().checked_add(length) {
+        Some(e) => e,
+        None => {
+            reply = format!("ban length too long: `{}`.", penalties::format_duration(length));
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    match database.add_penalty(player, Offence::Banned, expires_at) {
+        Ok (0) => (),
+        Ok (r) => {
+            reply = format!("database returned an unkown result when calling `add_penalty()`: `{}`", r);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        },
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    if let Err(e) = database.clear_mm_user(player) {
+        return Err(CommandError::from(format!("{}", e)));
+    }
+    queues.lock().remove_everywhere(player);
+    reply = format!("{} has been banned from queueing for {}.", lobby::mention(player), penalties::format_duration(length));
+    let _ = message.channel_id.say(&context.http, &reply);
+    Ok(())
+}
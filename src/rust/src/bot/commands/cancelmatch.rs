@@ -0,0 +1,41 @@
+use crate::bot::lobby;
+use crate::match_making::matches::Matches;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// cancel an active or disputed match without a result, leaving the ratings
+// of its players as they are: `!cancelmatch <match id>`
+pub fn cancelmatch(context: &mut Context, message: &Message, mut args: Args) -> CommandResult {
+    let matches = context.data.read().get::<Matches>().cloned().unwrap();
+    let reply;
+    let match_id = match args.single::<i32>() {
+        Ok (m) => m,
+        Err(_) => {
+            reply = "usage: `!cancelmatch <match id>`".to_string();
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let channel = matches.lock().find_by_id(match_id)
+        .map(|g| ChannelId(g.channel))
+        .unwrap_or(message.channel_id);
+    if let Err(e) = lobby::cancel_match(&context.http, &context.data, channel, match_id) {
+        reply = format!("{}", e);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    matches.lock().remove(match_id);
+    if channel != message.channel_id {
+        let _ = message.channel_id.say(&context.http, format!("match #{} has been cancelled.", match_id));
+    }
+    Ok(())
+}
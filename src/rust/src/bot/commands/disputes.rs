@@ -0,0 +1,47 @@
+use crate::database::Database;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+use std::time::SystemTime;
+
+#[command]
+// list every match whose result was disputed by the teams and is awaiting
+// a decision by an admin
+pub fn disputes(context: &mut Context, message: &Message, _: Args) -> CommandResult {
+    let database = context.data.read().get::<Database>().cloned().unwrap();
+    let reply;
+    let disputes = match database.get_disputed_matches() {
+        Ok (d) => d,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    if disputes.is_empty() {
+        let _ = message.channel_id.say(&context.http, "there are no disputed matches.");
+        return Ok(());
+    }
+    let lines: Vec<String> = disputes.iter()
+        .map(|d| {
+            let minutes = SystemTime::now()
+                .duration_since(d.created_at)
+                .map(|d| d.as_secs() / 60)
+                .unwrap_or(0);
+            format!("match #{} (`{}`), formed {} minutes ago", d.match_id, d.group, minutes)
+        })
+        .collect();
+    reply = format!(
+        "disputed matches:\n{}\ndecide them with `!setresult <match id> axis|allies|draw` or `!cancelmatch <match id>`.",
+        lines.join("\n")
+    );
+    let _ = message.channel_id.say(&context.http, &reply);
+    Ok(())
+}
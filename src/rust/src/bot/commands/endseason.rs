@@ -0,0 +1,93 @@
+use crate::bot:: {
+    channels,
+    leaderboards::Leaderboards,
+    lobby
+};
+use crate::database::Database;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+/// number of top finishers given the mentioned role when no count is specified.
+const TOP_FINISHERS: usize = 3;
+
+#[command]
+// end the running season of a match making group and archive its final
+// leaderboard: `!endseason [group] [@role] [count]`. a mentioned role is
+// given to the top finishers of the season, the top 3 unless a count is
+// specified. the group defaults to the match making group hosted in the
+// channel that this function was called from
+pub fn endseason(context: &mut Context, message: &Message, args: Args) -> CommandResult {
+    let database = context.data.read().get::<Database>().cloned().unwrap();
+    let leaderboards = context.data.read().get::<Leaderboards>().cloned().unwrap();
+    let reply;
+    let role = message.mention_roles.first().cloned();
+    let mut group = None;
+    let mut count = TOP_FINISHERS;
+    for token in args.rest().split_whitespace().filter(|t| !t.starts_with("<@&")) {
+        match token.parse::<usize>() {
+            Ok (c) => count = c,
+            Err(_) => group = Some(token.to_lowercase())
+        };
+    }
+    let group = match group.map(Ok).unwrap_or_else(|| channels::group(context, message.channel_id)) {
+        Ok (g) => g,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let guild = match (role, message.guild_id) {
+        (Some(_), None) => {
+            reply = "roles can only be given from a server channel.".to_string();
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+        (_, guild) => guild
+    };
+    let provisional = leaderboards.lock().provisional_games;
+    let season = match database.end_season(&group, provisional) {
+        Ok (s) => s,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let mut lines = vec![format!(
+        "season {} of `{}` has ended, its final standings can be seen with `!leaderboard {} --season {}`.",
+        season, group, group, season
+    )];
+    if let (Some(role), Some(guild)) = (role, guild) {
+        let top = match database.get_season_standings(&group, season, count, 0) {
+            Ok ((standings, _)) => standings,
+            Err(e) => {
+                reply = format!("{}", e);
+                let _ = message.channel_id.say(&context.http, &reply);
+                return Err(CommandError::from(reply));
+            }
+        };
+        let (given, failed): (Vec<u64>, Vec<u64>) = top.iter()
+            .map(|s| s.discord_uuid)
+            .partition(|u| context.http.add_member_role(*guild.as_u64(), *u, *role.as_u64()).is_ok());
+        if !given.is_empty() {
+            let mentions: Vec<String> = given.iter().map(|u| lobby::mention(*u)).collect();
+            lines.push(format!("the top finishers were given the role: {}", mentions.join(", ")));
+        }
+        if !failed.is_empty() {
+            let mentions: Vec<String> = failed.iter().map(|u| lobby::mention(*u)).collect();
+            lines.push(format!("the role could not be given to: {}", mentions.join(", ")));
+        }
+    }
+    reply = lines.join("\n");
+    let _ = message.channel_id.say(&context.http, &reply);
+    Ok(())
+}
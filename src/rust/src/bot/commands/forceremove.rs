@@ -0,0 +1,68 @@
+use crate::bot:: {
+    channels,
+    lobby
+};
+use crate::database::Database;
+use crate::match_making::queue::Queues;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// unsubscribe the mentioned player from a match making group on their behalf:
+// `!forceremove @player [group]`. the group defaults to the match making group
+// hosted in the channel that this function was called from
+pub fn forceremove(context: &mut Context, message: &Message, mut args: Args) -> CommandResult {
+    let database = context.data.read().get::<Database>().cloned().unwrap();
+    let queues = context.data.read().get::<Queues>().cloned().unwrap();
+    let reply;
+    let player = match args.single::<UserId>() {
+        Ok (p) => *p.as_u64(),
+        Err(_) => {
+            reply = "usage: `!forceremove @player [group]`".to_string();
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let group = match args.single::<String>() {
+        Ok (g) => Ok (g.to_lowercase()),
+        Err(_) => channels::group(context, message.channel_id)
+    };
+    let group = match group {
+        Ok (g) => g,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let result = match database.remove_mm_user(player, &group) {
+        Ok (r) => r,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply))
+        }
+    };
+    match result {
+        0 => {
+            queues.lock().unsubscribe(&group, player);
+            reply = format!("{} has been unsubscribed from the `{}` match making group by an admin.", lobby::mention(player), group);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Ok(());
+        },
+        1 => reply = format!("failed to add {} to the database.", lobby::mention(player)),
+        2 => reply = format!("match making group: `{}` does not exist.", group),
+        3 => reply = format!("{} is not subscribed to match making group: `{}`", lobby::mention(player), group),
+        _ => reply = format!("database returned an unkown result when calling `remove_match_making_user()`: `{}`", result)
+    };
+    let _ = message.channel_id.say(&context.http, &reply);
+    Err(CommandError::from(reply))
+}
@@ -0,0 +1,58 @@
+use crate::bot:: {
+    channels,
+    leaderboards
+};
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// post the leaderboard of a match making group:
+// `!leaderboard [group] [page] [--season N]`. the group defaults to the match
+// making group hosted in the channel that this function was called from, and a
+// season shows the archived standings of that ended season
+pub fn leaderboard(context: &mut Context, message: &Message, args: Args) -> CommandResult {
+    let reply;
+    let mut group = None;
+    let mut season = None;
+    let mut page = 1;
+    let mut tokens = args.rest().split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token == "--season" {
+            match tokens.next().and_then(|t| t.parse::<i32>().ok()) {
+                Some(s) => season = Some(s),
+                None => {
+                    reply = "usage: `!leaderboard [group] [page] [--season N]`".to_string();
+                    let _ = message.channel_id.say(&context.http, &reply);
+                    return Err(CommandError::from(reply));
+                }
+            };
+            continue;
+        }
+        match token.parse::<usize>() {
+            Ok (p) => page = p,
+            Err(_) => group = Some(token.to_lowercase())
+        };
+    }
+    let group = match group.map(Ok).unwrap_or_else(|| channels::group(context, message.channel_id)) {
+        Ok (g) => g,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    if let Err(e) = leaderboards::post(&context.http, &context.data, message.channel_id, &group, season, page) {
+        reply = format!("{}", e);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::match_making::links:: {
+    self,
+    Links
+};
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+use std::time::SystemTime;
+
+#[command]
+// send the user calling this function a one-time token by direct message.
+// saying the token in the chat of one of our servers links the in-game
+// identity of the player to their discord account
+pub fn link(context: &mut Context, message: &Message, _: Args) -> CommandResult {
+    let links = context.data.read().get::<Links>().cloned().unwrap();
+    let reply;
+    let token = links.lock().issue(*message.author.id.as_u64(), SystemTime::now());
+    let instructions = format!(
+        "say `{}` in the chat of any of our servers within {} minutes to link your in-game identity to your discord account.",
+        token, links::TOKEN_TIMEOUT.as_secs() / 60
+    );
+    if let Err(e) = message.author.direct_message(&context.http, |m| m.content(&instructions)) {
+        reply = format!("`{}` could not be sent a link token: {}", message.author.name, e);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    let _ = message.channel_id.say(&context.http, format!("`{}` has been sent a link token.", message.author.name));
+    Ok(())
+}
@@ -1,3 +1,23 @@
+pub mod abandon;
+pub mod adjustrating;
+pub mod ban;
+pub mod cancelmatch;
+pub mod disputes;
+pub mod endseason;
+pub mod forceadd;
+pub mod forceremove;
+pub mod leaderboard;
+pub mod link;
+pub mod pick;
 pub mod ping;
+pub mod ready;
+pub mod report;
+pub mod setresult;
+pub mod startseason;
+pub mod stats;
+pub mod status;
 pub mod subscribe;
+pub mod unban;
 pub mod unsubscribe;
+pub mod vote;
+pub mod who;
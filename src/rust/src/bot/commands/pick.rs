@@ -0,0 +1,31 @@
+use crate::bot::lobby;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// pick the mentioned player for the team of the draft captain calling this function
+pub fn pick(context: &mut Context, message: &Message, mut args: Args) -> CommandResult {
+    let reply;
+    let player = match args.single::<UserId>() {
+        Ok (p) => p,
+        Err(_) => {
+            reply = "usage: `!pick @player`".to_string();
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    if let Err(e) = lobby::draft_pick(&context.http, &context.data, *message.author.id.as_u64(), *player.as_u64()) {
+        reply = format!("{}", e);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    Ok(())
+}
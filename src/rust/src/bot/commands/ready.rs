@@ -0,0 +1,22 @@
+use crate::bot::lobby;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// acknowledge the ready check the user calling this function was popped into
+pub fn ready(context: &mut Context, message: &Message, _: Args) -> CommandResult {
+    if let Err(e) = lobby::player_ready(&context.http, &context.data, *message.author.id.as_u64()) {
+        let reply = format!("{}", e);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    Ok(())
+}
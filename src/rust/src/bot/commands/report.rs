@@ -0,0 +1,83 @@
+use crate::bot::lobby;
+use crate::match_making::matches:: {
+    Matches,
+    Outcome,
+    ReportStatus
+};
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// report the result of the match the user calling this function is playing in
+// on behalf of their team. the result is applied once both teams agree on it
+// and sent to the admins if they do not
+pub fn report(context: &mut Context, message: &Message, args: Args) -> CommandResult {
+    let matches = context.data.read().get::<Matches>().cloned().unwrap();
+    let reply;
+    let outcome = match Outcome::parse(args.rest()) {
+        Ok (o) => o,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let (match_id, group, channel, team, status) = {
+        let mut matches = matches.lock();
+        let game = match matches.find(*message.author.id.as_u64()) {
+            Some(m) => m,
+            None => {
+                reply = format!("`{}` is not playing in a match.", message.author.name);
+                let _ = message.channel_id.say(&context.http, &reply);
+                return Err(CommandError::from(reply));
+            }
+        };
+        let team = game.team_of(*message.author.id.as_u64()).unwrap();
+        let status = match game.report(*message.author.id.as_u64(), outcome) {
+            Ok (s) => s,
+            Err(e) => {
+                reply = format!("{}", e);
+                let _ = message.channel_id.say(&context.http, &reply);
+                return Err(CommandError::from(reply));
+            }
+        };
+        lobby::save_match_state(&context.data, game);
+        let match_id = game.id;
+        let group = game.group.clone();
+        let channel = ChannelId(game.channel);
+        (match_id, group, channel, team, status)
+    };
+    let result = match status {
+        ReportStatus::Pending => {
+            let _ = message.channel_id.say(&context.http, format!(
+                "**{}** reported a {} for match #{} (`{}`). waiting for the other team to confirm.",
+                team.as_str(), outcome.as_str(), match_id, group
+            ));
+            return Ok(());
+        },
+        ReportStatus::Confirmed(r) => lobby::complete_match(&context.http, &context.data, channel, match_id, r),
+        ReportStatus::Disputed => lobby::dispute_match(&context.http, &context.data, channel, match_id)
+    };
+    // the match stays active until the database has it, so it can be
+    // reported again if that failed. a match the database already finished
+    // is dropped by the lobby
+    if let Err(e) = result {
+        reply = format!("{}", e);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    let finished = matches.lock().remove(match_id);
+    if let Some(game) = &finished {
+        lobby::record_stats(&context.data, game);
+        lobby::penalize_departures(&context.http, &context.data, game);
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::bot::lobby;
+use crate::match_making::matches:: {
+    Matches,
+    MatchResult
+};
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// decide the result of an active or disputed match and apply its rating
+// changes: `!setresult <match id> axis|allies|draw`
+pub fn setresult(context: &mut Context, message: &Message, mut args: Args) -> CommandResult {
+    let matches = context.data.read().get::<Matches>().cloned().unwrap();
+    let reply;
+    let (match_id, result) = match (args.single::<i32>(), args.single::<String>()) {
+        (Ok (m), Ok (r)) => (m, r),
+        _ => {
+            reply = "usage: `!setresult <match id> axis|allies|draw`".to_string();
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let result = match MatchResult::parse(&result) {
+        Ok (r) => r,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let channel = matches.lock().find_by_id(match_id)
+        .map(|g| ChannelId(g.channel))
+        .unwrap_or(message.channel_id);
+    if let Err(e) = lobby::complete_match(&context.http, &context.data, channel, match_id, result) {
+        reply = format!("{}", e);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    let finished = matches.lock().remove(match_id);
+    if let Some(game) = &finished {
+        lobby::record_stats(&context.data, game);
+        lobby::penalize_departures(&context.http, &context.data, game);
+    }
+    if channel != message.channel_id {
+        let _ = message.channel_id.say(&context.http, format!("the result of match #{} has been set.", match_id));
+    }
+    Ok(())
+}
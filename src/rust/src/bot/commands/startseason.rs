@@ -0,0 +1,45 @@
+use crate::bot::channels;
+use crate::database::Database;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// start a new season in a match making group: `!startseason [group]`. every
+// rating in the group is moved towards the mean and the leaderboard only
+// counts matches formed from now on. the group defaults to the match making
+// group hosted in the channel that this function was called from
+pub fn startseason(context: &mut Context, message: &Message, args: Args) -> CommandResult {
+    let database = context.data.read().get::<Database>().cloned().unwrap();
+    let reply;
+    let group = match args.rest().split_whitespace().next() {
+        Some(g) => Ok (g.to_lowercase()),
+        None => channels::group(context, message.channel_id)
+    };
+    let group = match group {
+        Ok (g) => g,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let season = match database.start_season(&group) {
+        Ok (s) => s,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    reply = format!("season {} of `{}` has started and ratings have been moved towards the mean.", season, group);
+    let _ = message.channel_id.say(&context.http, &reply);
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::bot::lobby;
+use crate::database::Database;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+// number of matches summarised as recent form
+const RECENT_MATCHES: usize = 5;
+
+#[command]
+// show the lifetime statistics and recent form of the user calling this
+// function, or of the mentioned user, optionally limited to a single match
+// making group: `!stats [@user] [group]`
+pub fn stats(context: &mut Context, message: &Message, mut args: Args) -> CommandResult {
+    let database = context.data.read().get::<Database>().cloned().unwrap();
+    let reply;
+    let user = match args.single::<UserId>() {
+        Ok (u) => *u.as_u64(),
+        Err(_) => *message.author.id.as_u64()
+    };
+    let group = args.rest().trim().to_lowercase();
+    let group = if group.is_empty() { None } else { Some(group.as_str()) };
+    let lifetime = match database.get_player_stats(user, group) {
+        Ok (l) => l,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let recent = match database.get_recent_player_matches(user, group, RECENT_MATCHES) {
+        Ok (r) => r,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let scope = match group {
+        Some(g) => format!("`{}`", g),
+        None => "every group".to_string()
+    };
+    if lifetime.matches == 0 {
+        let _ = message.channel_id.say(&context.http, format!("{} has not completed a match in {}.", lobby::mention(user), scope));
+        return Ok(());
+    }
+    let ratio = lifetime.kills as f64 / lifetime.deaths.max(1) as f64;
+    let accuracy = 100.0 * lifetime.hits as f64 / lifetime.shots.max(1) as f64;
+    let form: Vec<String> = recent.iter()
+        .map(|m| match (m.kills, m.deaths) {
+            (Some(k), Some(d)) => format!("{} (#{}, {}/{})", m.outcome, m.match_id, k, d),
+            _ => format!("{} (#{})", m.outcome, m.match_id)
+        })
+        .collect();
+    reply = format!(
+        "statistics of {} in {}:\n\
+        **lifetime**: {} matches ({} won, {} lost, {} drawn)\n\
+        {} kills, {} deaths ({:.2} k/d), {} gibs, {} revives, {} objectives\n\
+        {} damage given, {} damage received, {:.1}% accuracy, {} headshots\n\
+        **recent form**: {}",
+        lobby::mention(user), scope,
+        lifetime.matches, lifetime.wins, lifetime.losses, lifetime.draws,
+        lifetime.kills, lifetime.deaths, ratio, lifetime.gibs, lifetime.revives, lifetime.objectives,
+        lifetime.damage_given, lifetime.damage_received, accuracy, lifetime.headshots,
+        form.join(", ")
+    );
+    let _ = message.channel_id.say(&context.http, &reply);
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::database::Database;
+use crate::match_making::servers::Servers;
+use et_mm_bot::et::query;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+use std::time::Duration;
+
+// how long to wait for a game server to answer a status query
+const QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[command]
+// query ET servers for their current map and players so players can see
+// whether they are free before a match. without arguments every configured
+// server is listed, otherwise the named server is queried. only configured
+// servers are queried, so players cannot aim the bot at other hosts
+pub fn status(context: &mut Context, message: &Message, args: Args) -> CommandResult {
+    let servers = context.data.read().get::<Servers>().cloned().unwrap();
+    let reply;
+    let server = args.rest().trim();
+    if server.is_empty() {
+        return list_servers(context, message, &servers);
+    }
+    let address = match servers.find(server) {
+        Some(s) => s.address(),
+        None => {
+            reply = format!("`{}` is an unknown server.", server);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let status = match query::status(&address, QUERY_TIMEOUT) {
+        Ok (s) => s,
+        Err(e) => {
+            reply = format!("`{}`: {}", server, e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let mut lines = vec![
+        format!(
+            "**{}** (`{}`): `{}` {} on `{}`, {}/{} players{}",
+            query::strip_colors(&status.hostname),
+            address,
+            status.gamename,
+            query::gametype_name(status.gametype),
+            status.map,
+            status.players.len(),
+            status.max_clients,
+            if status.needs_password { ", password protected" } else { "" }
+        )
+    ];
+    if status.is_empty() {
+        lines.push(String::from("the server is free."));
+    }
+    for player in &status.players {
+        lines.push(format!("`{}` score {}, ping {}", player.clean_name(), player.score, player.ping));
+    }
+    let _ = message.channel_id.say(&context.http, lines.join("\n"));
+    Ok(())
+}
+
+// list every configured server along with the match it is allocated to or
+// the number of players on it
+fn list_servers(context: &mut Context, message: &Message, servers: &Servers) -> CommandResult {
+    let database = context.data.read().get::<Database>().cloned().unwrap();
+    let reply;
+    if servers.all().is_empty() {
+        reply = String::from("no game servers are configured.");
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    let busy = match database.get_busy_servers() {
+        Ok (b) => b,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let lines: Vec<String> = servers.all().iter()
+        .map(|s| {
+            let state = match busy.get(&s.name) {
+                Some(m) => format!("playing match #{}", m),
+                None => match query::status(&s.address(), QUERY_TIMEOUT) {
+                    Ok (q) if q.is_empty() => String::from("free"),
+                    Ok (q) => format!("{} players on `{}`", q.players.len(), q.map),
+                    Err(e) => format!("{}", e)
+                }
+            };
+            format!("`{}` ({}, {}): {}", s.name, s.region, s.address(), state)
+        })
+        .collect();
+    let _ = message.channel_id.say(&context.http, lines.join("\n"));
+    Ok(())
+}
@@ -1,4 +1,12 @@
+use crate::bot:: {
+    channels,
+    lobby
+};
 use crate::database::Database;
+use crate::match_making:: {
+    penalties,
+    queue::Queues
+};
 use serenity:: {
     framework::standard:: {
         Args,
@@ -9,40 +17,86 @@ use serenity:: {
     model::prelude::*,
     prelude::*
 };
+use std::time::SystemTime;
 
 #[command]
-// subscribe the user calling this function to the match making group matching the name
-// of the channel that this function was called from
-pub fn subscribe(context: &mut Context, message: &Message, _: Args) -> CommandResult {
+// subscribe the user calling this function to match making groups:
+// `!subscribe [groups...|all]`. the groups default to those hosted in the
+// channel that this function was called from. if a match making queue reaches
+// capacity it pops into a ready check and no further groups are subscribed
+pub fn subscribe(context: &mut Context, message: &Message, args: Args) -> CommandResult {
     let database = context.data.read().get::<Database>().cloned().unwrap();
+    let queues = context.data.read().get::<Queues>().cloned().unwrap();
     let reply;
-    let group = match message.channel_id.name(&context) {
-        Some(g) => g,
-        None => {
-            reply = format!("error retrieving channel name.");
+    let penalties = match database.get_penalties(*message.author.id.as_u64()) {
+        Ok (p) => p,
+        Err(e) => {
+            reply = format!("{}", e);
             let _ = message.channel_id.say(&context.http, &reply);
             return Err(CommandError::from(reply));
         }
     };
-    let result = match database.add_mm_user(*message.author.id.as_u64(), &group) {
-        Ok (r) => r,
+    let now = SystemTime::now();
+    if let Some(penalty) = penalties::active(&penalties, now) {
+        let remaining = penalty.expires_at.duration_since(now).unwrap_or_default();
+        reply = format!(
+            "`{}` {} and cannot queue for another {}.",
+            message.author.name, penalty.offence.describe(), penalties::format_duration(remaining)
+        );
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    if lobby::is_busy(&context.data, *message.author.id.as_u64()) {
+        reply = format!("`{}` is already in a ready check, map vote or draft.", message.author.name);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    let groups = match channels::requested(context, message.channel_id, args.rest()) {
+        Ok (g) => g,
         Err(e) => {
             reply = format!("{}", e);
             let _ = message.channel_id.say(&context.http, &reply);
-            return Err(CommandError::from(reply))
+            return Err(CommandError::from(reply));
         }
     };
-    match result {
-        0 => {
-            reply = format!("`{}` has been subscribed to the `{}` match making group.", message.author.name, group);
-            let _ = message.channel_id.say(&context.http, &reply);
-            return Ok(());
-        },
-        1 => reply = format!("failed to add `{}` to the database.", message.author.name),
-        2 => reply = format!("match making group: `{}` does not exist.", group),
-        3 => reply = format!("`{}` is already subscribed to match making group: `{}`", message.author.name, group),
-        _ => reply = format!("database returned an unkown result when calling `add_match_making_user()`: `{}`", result)
-    };
+    let player = *message.author.id.as_u64();
+    let mut lines = Vec::new();
+    let mut subscribed = false;
+    let mut popped = None;
+    for group in groups.iter() {
+        let result = match database.add_mm_user(player, group) {
+            Ok (r) => r,
+            Err(e) => {
+                lines.push(format!("{}", e));
+                continue;
+            }
+        };
+        match result {
+            0 => {
+                subscribed = true;
+                lines.push(format!("`{}` has been subscribed to the `{}` match making group.", message.author.name, group));
+                match queues.lock().subscribe(group, player) {
+                    Ok (Some(players)) => popped = Some((group.clone(), players)),
+                    Ok (None) => (),
+                    Err(e) => lines.push(format!("{}", e))
+                };
+            },
+            1 => lines.push(format!("failed to add `{}` to the database.", message.author.name)),
+            2 => lines.push(format!("match making group: `{}` does not exist.", group)),
+            3 => lines.push(format!("`{}` is already subscribed to match making group: `{}`", message.author.name, group)),
+            _ => lines.push(format!("database returned an unkown result when calling `add_match_making_user()`: `{}`", result))
+        };
+        if popped.is_some() {
+            break;
+        }
+    }
+    reply = lines.join("\n");
     let _ = message.channel_id.say(&context.http, &reply);
-    Err(CommandError::from(reply))
+    if let Some((group, players)) = popped {
+        lobby::queue_popped(&context.http, &context.data, message.channel_id, &group, &players);
+    }
+    if !subscribed {
+        return Err(CommandError::from(reply));
+    }
+    Ok(())
 }
@@ -0,0 +1,39 @@
+use crate::bot::lobby;
+use crate::database::Database;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// lift every queue ban of the mentioned player, whether it was given by an
+// admin or for dodging or leaving matches: `!unban @player`
+pub fn unban(context: &mut Context, message: &Message, mut args: Args) -> CommandResult {
+    let database = context.data.read().get::<Database>().cloned().unwrap();
+    let reply;
+    let player = match args.single::<UserId>() {
+        Ok (p) => *p.as_u64(),
+        Err(_) => {
+            reply = "usage: `!unban @player`".to_string();
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    match database.lift_penalties(player) {
+        Ok (0) => reply = format!("{} is not banned.", lobby::mention(player)),
+        Ok (_) => reply = format!("{} may queue again.", lobby::mention(player)),
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let _ = message.channel_id.say(&context.http, &reply);
+    Ok(())
+}
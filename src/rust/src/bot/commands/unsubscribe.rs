@@ -1,4 +1,6 @@
+use crate::bot::channels;
 use crate::database::Database;
+use crate::match_making::queue::Queues;
 use serenity:: {
     framework::standard:: {
         Args,
@@ -11,38 +13,59 @@ use serenity:: {
 };
 
 #[command]
-// subscribe the user calling this function to the match making group matching the name
-// of the channel that this function was called from
-pub fn unsubscribe(context: &mut Context, message: &Message, _: Args) -> CommandResult {
+// unsubscribe the user calling this function from match making groups:
+// `!unsubscribe [groups...|all]`. the groups default to those hosted in the
+// channel that this function was called from
+pub fn unsubscribe(context: &mut Context, message: &Message, args: Args) -> CommandResult {
     let database = context.data.read().get::<Database>().cloned().unwrap();
+    let queues = context.data.read().get::<Queues>().cloned().unwrap();
     let reply;
-    let group = match message.channel_id.name(&context) {
-        Some(g) => g,
-        None => {
-            reply = format!("error retrieving channel name.");
+    let player = *message.author.id.as_u64();
+    if args.rest().split_whitespace().any(|t| t.eq_ignore_ascii_case("all")) {
+        if let Err(e) = database.clear_mm_user(player) {
+            reply = format!("{}", e);
             let _ = message.channel_id.say(&context.http, &reply);
             return Err(CommandError::from(reply));
         }
-    };
-    let result = match database.remove_mm_user(*message.author.id.as_u64(), &group) {
-        Ok (r) => r,
+        queues.lock().remove_everywhere(player);
+        reply = format!("`{}` has been unsubscribed from every match making group.", message.author.name);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Ok(());
+    }
+    let groups = match channels::requested(context, message.channel_id, args.rest()) {
+        Ok (g) => g,
         Err(e) => {
             reply = format!("{}", e);
             let _ = message.channel_id.say(&context.http, &reply);
-            return Err(CommandError::from(reply))
+            return Err(CommandError::from(reply));
         }
     };
-    match result {
-        0 => {
-            reply = format!("`{}` has been unsubscribed from the `{}` match making group.", message.author.name, group);
-            let _ = message.channel_id.say(&context.http, &reply);
-            return Ok(());
-        },
-        1 => reply = format!("failed to add `{}` to the database.", message.author.name),
-        2 => reply = format!("match making group: `{}` does not exist.", group),
-        3 => reply = format!("`{}` is not subscribed to match making group: `{}`", message.author.name, group),
-        _ => reply = format!("database returned an unkown result when calling `remove_match_making_user()`: `{}`", result)
-    };
+    let mut lines = Vec::new();
+    let mut unsubscribed = false;
+    for group in groups.iter() {
+        let result = match database.remove_mm_user(player, group) {
+            Ok (r) => r,
+            Err(e) => {
+                lines.push(format!("{}", e));
+                continue;
+            }
+        };
+        match result {
+            0 => {
+                unsubscribed = true;
+                queues.lock().unsubscribe(group, player);
+                lines.push(format!("`{}` has been unsubscribed from the `{}` match making group.", message.author.name, group));
+            },
+            1 => lines.push(format!("failed to add `{}` to the database.", message.author.name)),
+            2 => lines.push(format!("match making group: `{}` does not exist.", group)),
+            3 => lines.push(format!("`{}` is not subscribed to match making group: `{}`", message.author.name, group)),
+            _ => lines.push(format!("database returned an unkown result when calling `remove_match_making_user()`: `{}`", result))
+        };
+    }
+    reply = lines.join("\n");
     let _ = message.channel_id.say(&context.http, &reply);
-    Err(CommandError::from(reply))
+    if !unsubscribed {
+        return Err(CommandError::from(reply));
+    }
+    Ok(())
 }
@@ -0,0 +1,25 @@
+use crate::bot::lobby;
+use serenity:: {
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+
+#[command]
+// vote for a map in the map vote the user calling this function is part of
+pub fn vote(context: &mut Context, message: &Message, args: Args) -> CommandResult {
+    let reply;
+    if let Err(e) = lobby::vote_map(&context.data, *message.author.id.as_u64(), args.rest()) {
+        reply = format!("{}", e);
+        let _ = message.channel_id.say(&context.http, &reply);
+        return Err(CommandError::from(reply));
+    }
+    reply = format!("`{}` voted for `{}`.", message.author.name, args.rest().trim().to_lowercase());
+    let _ = message.channel_id.say(&context.http, &reply);
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use crate::bot:: {
+    channels,
+    lobby
+};
+use crate::database::Database;
+use crate::match_making:: {
+    penalties,
+    queue::Queues
+};
+use serenity:: {
+    builder::CreateEmbed,
+    framework::standard:: {
+        Args,
+        CommandResult,
+        CommandError,
+        macros::command
+    },
+    model::prelude::*,
+    prelude::*
+};
+use std::time::SystemTime;
+
+#[command]
+#[aliases(queue)]
+// list the players waiting in match making queues, how long each of them has
+// waited and how many more players are needed for the queue to pop:
+// `!who [groups...|all]`. without arguments every match making group is listed
+pub fn who(context: &mut Context, message: &Message, args: Args) -> CommandResult {
+    let database = context.data.read().get::<Database>().cloned().unwrap();
+    let queues = context.data.read().get::<Queues>().cloned().unwrap();
+    let reply;
+    let groups = match args.rest().trim() {
+        "" => Ok (queues.lock().groups()),
+        text => channels::requested(context, message.channel_id, text)
+    };
+    let groups = match groups {
+        Ok (g) => g,
+        Err(e) => {
+            reply = format!("{}", e);
+            let _ = message.channel_id.say(&context.http, &reply);
+            return Err(CommandError::from(reply));
+        }
+    };
+    let now = SystemTime::now();
+    let mut embed = CreateEmbed::default();
+    embed.title("match making queues");
+    for group in groups.iter() {
+        let capacity = match queues.lock().capacity(group) {
+            Some(c) => c,
+            None => {
+                reply = format!("no queue exists for match making group: {}", group);
+                let _ = message.channel_id.say(&context.http, &reply);
+                return Err(CommandError::from(reply));
+            }
+        };
+        let subscriptions = match database.get_mm_subscriptions(group) {
+            Ok (s) => s,
+            Err(e) => {
+                reply = format!("{}", e);
+                let _ = message.channel_id.say(&context.http, &reply);
+                return Err(CommandError::from(reply));
+            }
+        };
+        let mut lines: Vec<String> = subscriptions.iter()
+            .map(|s| format!(
+                "{} waiting {}",
+                lobby::mention(s.discord_uuid),
+                penalties::format_duration(now.duration_since(s.subscribed_at).unwrap_or_default())
+            ))
+            .collect();
+        if lines.is_empty() {
+            lines.push(String::from("nobody is waiting."));
+        }
+        let name = format!(
+            "{} ({}/{}, {} more needed)",
+            group, subscriptions.len(), capacity, capacity.saturating_sub(subscriptions.len())
+        );
+        embed.field(name, lines.join("\n"), false);
+    }
+    let _ = message.channel_id.send_message(&context.http, |m| m.embed(|e| {
+        *e = embed;
+        e
+    }));
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use crate::bot::lobby;
+use crate::database::Database;
+use serenity:: {
+    builder::CreateEmbed,
+    http::Http,
+    model::prelude::*,
+    prelude::*
+};
+use std:: {
+    collections::VecDeque,
+    error::Error
+};
+
+/// emoji players react with to show the previous page of a leaderboard.
+const PREVIOUS_EMOJI: &str = "◀️";
+
+/// emoji players react with to show the next page of a leaderboard.
+const NEXT_EMOJI: &str = "▶️";
+
+/// number of players shown on each page of a leaderboard.
+const PAGE_SIZE: usize = 10;
+
+/// number of posted leaderboards that can still be paged through.
+const TRACKED_LEADERBOARDS: usize = 50;
+
+/// Leaderboard structure for a leaderboard posted in a channel
+///
+/// # Members
+///
+///     ```
+///     channel: discord channel id the leaderboard was posted in
+///     message: discord message id of the leaderboard
+///     group: match making group the leaderboard ranks
+///     season: ended season whose archived standings are shown, if any
+///     page: page currently shown, starting at 1
+///     ```
+struct Leaderboard {
+    channel: u64,
+    message: u64,
+    group: String,
+    season: Option<i32>,
+    page: usize
+}
+
+/// Leaderboards structure for the posted leaderboards players can page through
+///
+/// # Members
+///
+///     ```
+///     provisional_games: completed matches a player needs to be ranked
+///     posted: most recently posted leaderboards, oldest first
+///     ```
+pub struct Leaderboards {
+    pub provisional_games: usize,
+    posted: VecDeque<Leaderboard>
+}
+
+// Leaderboards implementation
+impl Leaderboards {
+    /// constructs an empty set of posted leaderboards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let leaderboards = leaderboards::Leaderboards::construct(5);
+    /// ```
+    pub fn construct (provisional_games: usize) -> Self {
+        Self {
+            provisional_games,
+            posted: VecDeque::new()
+        }
+    }
+    /// starts tracking a posted leaderboard, forgetting the oldest one once
+    /// too many are tracked.
+    fn track (&mut self, leaderboard: Leaderboard) {
+        if self.posted.len() >= TRACKED_LEADERBOARDS {
+            self.posted.pop_front();
+        }
+        self.posted.push_back(leaderboard);
+    }
+    /// returns the posted leaderboard with the specified message id.
+    fn find (&mut self, message: u64) -> Option<&mut Leaderboard> {
+        self.posted.iter_mut().find(|l| l.message == message)
+    }
+}
+
+/// fills an embed with a page of the leaderboard of a match making group,
+/// or of the archived standings of one of its ended seasons. returns the
+/// page actually shown, which is limited to the last page, and the number
+/// of pages.
+fn render (data: &RwLock<ShareMap>, embed: &mut CreateEmbed, group: &str, season: Option<i32>, page: usize) -> Result<(usize, usize), Box<dyn Error>> {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let leaderboards = data.read().get::<Leaderboards>().cloned().unwrap();
+    let provisional = leaderboards.lock().provisional_games;
+    let fetch = |size: usize, offset: usize| match season {
+        Some(s) => database.get_season_standings(group, s, size, offset),
+        None => database.get_leaderboard(group, provisional, size, offset)
+    };
+    let mut page = page.max(1);
+    let (mut standings, mut ranked) = fetch(PAGE_SIZE, (page - 1) * PAGE_SIZE)?;
+    if standings.is_empty() && page > 1 {
+        // the page is past the end of the leaderboard, so show the last page
+        let (_, total) = fetch(1, 0)?;
+        page = (total as usize).div_ceil(PAGE_SIZE).max(1);
+        let (last, total) = fetch(PAGE_SIZE, (page - 1) * PAGE_SIZE)?;
+        standings = last;
+        ranked = total;
+    }
+    if ranked == 0 {
+        return Err(match season {
+            Some(s) => format!("`{}` has no archived standings for season {}.", group, s),
+            None => format!("nobody in `{}` has completed {} matches yet.", group, provisional)
+        }.into());
+    }
+    let pages = (ranked as usize).div_ceil(PAGE_SIZE);
+    let lines: Vec<String> = standings.iter()
+        .map(|s| format!(
+            "**{}.** {} **{:.0}**, {} games ({}-{}-{}), streak {}",
+            s.rank, lobby::mention(s.discord_uuid), s.rating, s.games, s.wins, s.losses, s.draws, s.streak
+        ))
+        .collect();
+    let (title, footer) = match season {
+        Some(s) => (
+            format!("`{}` season {} final standings", group, s),
+            format!("page {}/{}", page, pages)
+        ),
+        None => (
+            format!("`{}` leaderboard", group),
+            format!("page {}/{}, players with fewer than {} matches are provisional and not shown", page, pages, provisional)
+        )
+    };
+    embed.title(title)
+        .description(lines.join("\n"))
+        .footer(|f| f.text(footer));
+    Ok ((page, pages))
+}
+
+/// posts a page of the leaderboard of a match making group, or of the
+/// archived standings of an ended season when one is specified. leaderboards
+/// with several pages can be paged through with reactions.
+///
+/// # Example
+///
+/// ```
+/// leaderboards::post(&context.http, &context.data, message.channel_id, "6v6", None, 1).unwrap();
+/// ```
+pub fn post (http: &Http, data: &RwLock<ShareMap>, channel: ChannelId, group: &str, season: Option<i32>, page: usize) -> Result<(), Box<dyn Error>> {
+    let leaderboards = data.read().get::<Leaderboards>().cloned().unwrap();
+    let mut embed = CreateEmbed::default();
+    let (page, pages) = render(data, &mut embed, group, season, page)?;
+    let message = channel.send_message(http, |m| m.embed(|e| {
+        *e = embed;
+        e
+    }))?;
+    if pages > 1 {
+        let _ = channel.create_reaction(http, message.id, ReactionType::Unicode(PREVIOUS_EMOJI.to_string()));
+        let _ = channel.create_reaction(http, message.id, ReactionType::Unicode(NEXT_EMOJI.to_string()));
+    }
+    leaderboards.lock().track(Leaderboard {
+        channel: *channel.as_u64(),
+        message: *message.id.as_u64(),
+        group: group.to_string(),
+        season,
+        page
+    });
+    Ok (())
+}
+
+/// handles a reaction added to or removed from a message. the arrow emojis
+/// on a posted leaderboard show its previous or next page, and any other
+/// reaction is ignored. removals are handled too, so players can page
+/// repeatedly by toggling a reaction.
+pub fn reaction_toggled (http: &Http, data: &RwLock<ShareMap>, message: u64, emoji: &ReactionType) {
+    let leaderboards = data.read().get::<Leaderboards>().cloned().unwrap();
+    let step: isize = match emoji {
+        ReactionType::Unicode(e) if e == PREVIOUS_EMOJI => -1,
+        ReactionType::Unicode(e) if e == NEXT_EMOJI => 1,
+        _ => return
+    };
+    let (channel, group, season, page) = match leaderboards.lock().find(message) {
+        Some(l) => (ChannelId(l.channel), l.group.clone(), l.season, l.page),
+        None => return
+    };
+    let page = (page as isize + step).max(1) as usize;
+    let mut embed = CreateEmbed::default();
+    let page = match render(data, &mut embed, &group, season, page) {
+        Ok ((p, _)) => p,
+        Err(_) => return
+    };
+    let edited = channel.edit_message(http, MessageId(message), |m| m.embed(|e| {
+        *e = embed;
+        e
+    }));
+    if edited.is_ok() {
+        if let Some(l) = leaderboards.lock().find(message) {
+            l.page = page;
+        }
+    }
+}
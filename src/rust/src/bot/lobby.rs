@@ -0,0 +1,1226 @@
+use crate::database::Database;
+use crate::logger::Log;
+use crate::match_making:: {
+    balance:: {
+        self,
+        RatedPlayer,
+        Teams
+    },
+    draft:: {
+        self,
+        Draft,
+        Drafts,
+        TeamSelection
+    },
+    expiry:: {
+        Expiries,
+        Reason,
+        Warning
+    },
+    links:: {
+        Identity,
+        Links
+    },
+    map_vote:: {
+        self,
+        MapVote,
+        MapVotes
+    },
+    matches:: {
+        Match,
+        Matches,
+        MatchResult
+    },
+    penalties:: {
+        self,
+        Offence
+    },
+    queue::Queues,
+    rating,
+    ready_check:: {
+        ReadyCheck,
+        ReadyChecks
+    },
+    servers:: {
+        self,
+        Server,
+        Servers
+    }
+};
+use et_mm_bot::et:: {
+    log:: {
+        Clients,
+        Event
+    },
+    query,
+    rcon::Rcon
+};
+use serenity:: {
+    http::Http,
+    model::prelude::*,
+    prelude::*
+};
+use std:: {
+    error::Error,
+    time:: {
+        Duration,
+        SystemTime
+    }
+};
+
+/// emoji players react with to acknowledge a ready check or to say they are
+/// still there when asked.
+const READY_EMOJI: &str = "✅";
+
+/// emojis players react with to vote for the map at the same position.
+const MAP_EMOJIS: [&str; 9] = [
+    "1\u{fe0f}\u{20e3}", "2\u{fe0f}\u{20e3}", "3\u{fe0f}\u{20e3}",
+    "4\u{fe0f}\u{20e3}", "5\u{fe0f}\u{20e3}", "6\u{fe0f}\u{20e3}",
+    "7\u{fe0f}\u{20e3}", "8\u{fe0f}\u{20e3}", "9\u{fe0f}\u{20e3}"
+];
+
+/// how long to wait for a game server to answer a status query.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// least time players are given to act on a ready check, map vote or draft
+/// pick restored after a restart, since they could not act while the bot was
+/// down.
+const RESTORE_GRACE: Duration = Duration::from_secs(30);
+
+/// how long to wait for a game server to answer an rcon command. map changes
+/// are only answered once the new map has loaded.
+const RCON_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// formats a discord mention for a given discord uuid.
+pub fn mention (discord_uuid: u64) -> String {
+    format!("<@{}>", discord_uuid)
+}
+
+/// returns the number of seconds left until a deadline, rounded to the
+/// nearest second.
+fn seconds_left (deadline: SystemTime) -> u64 {
+    let left = deadline.duration_since(SystemTime::now()).unwrap_or_default();
+    (left.as_millis() as u64 + 500) / 1000
+}
+
+/// formats a team as a list of player mentions.
+fn roster (players: &[RatedPlayer]) -> String {
+    let mentions: Vec<String> = players.iter()
+        .map(|p| mention(p.discord_uuid))
+        .collect();
+    mentions.join(" ")
+}
+
+/// handles a popped match making queue. every popped player is unsubscribed
+/// from all match making groups in the database, since they have already been
+/// removed from every other queue in memory. a ready check is then posted in
+/// the channel the queue popped from, which every player must acknowledge with
+/// `!ready` or a reaction before the match is formed.
+///
+/// # Example
+///
+/// ```
+/// if let Some(players) = queues.subscribe(&group, discord_uuid).unwrap() {
+///     lobby::queue_popped(&context.http, &context.data, message.channel_id, &group, &players);
+/// }
+/// ```
+pub fn queue_popped (http: &Http, data: &RwLock<ShareMap>, channel: ChannelId, group: &str, players: &[u64]) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let checks = data.read().get::<ReadyChecks>().cloned().unwrap();
+    info!(log.logger, "\tmatch making queue popped"; "group" => group, "players" => players.len());
+    for player in players.iter() {
+        match database.clear_mm_user(*player) {
+            Ok (0) => (),
+            Ok (r) => warn!(log.logger, "\tunexpected result clearing popped player subscriptions: {}", r; "player" => player),
+            Err(e) => error!(log.logger, "\tfailed to clear popped player subscriptions: {}", e; "player" => player)
+        };
+    }
+    let check = ReadyCheck::construct(group, *channel.as_u64(), players, checks.lock().timeout);
+    let reply = describe_ready_check(&check, &format!("the `{}` match making queue has popped!", group));
+    // the ready check is added before it is posted, so its players count as
+    // busy straight away without the lock being held while discord answers
+    checks.lock().add(check);
+    let message = post_ready_check(http, data, channel, group, &reply);
+    let mut checks = checks.lock();
+    if let Some(c) = checks.find(players[0]) {
+        c.message = message;
+        save_ready_check(data, c);
+    }
+}
+
+/// formats a ready check, mentioning every player that is not ready yet.
+fn describe_ready_check (check: &ReadyCheck, headline: &str) -> String {
+    let mentions: Vec<String> = check.missing_players().iter()
+        .map(|p| mention(*p))
+        .collect();
+    format!(
+        "{} {}\ntype `!ready` or react with {} within {} seconds.",
+        headline, mentions.join(" "), READY_EMOJI, seconds_left(check.deadline)
+    )
+}
+
+/// posts a ready check in a channel with the ready emoji to react with, and
+/// returns the id of the message it was posted as.
+fn post_ready_check (http: &Http, data: &RwLock<ShareMap>, channel: ChannelId, group: &str, reply: &str) -> Option<u64> {
+    let log = data.read().get::<Log>().cloned().unwrap();
+    match channel.say(http, reply) {
+        Ok (m) => {
+            let _ = channel.create_reaction(http, m.id, ReactionType::Unicode(READY_EMOJI.to_string()));
+            Some(*m.id.as_u64())
+        },
+        Err(e) => {
+            error!(log.logger, "\tfailed to post ready check: {}", e; "group" => group);
+            None
+        }
+    }
+}
+
+/// stores a ready check so it survives a restart of the bot, recording the
+/// database id of a new one.
+fn save_ready_check (data: &RwLock<ShareMap>, check: &mut ReadyCheck) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    match database.save_ready_check(check) {
+        Ok (id) => check.id = Some(id),
+        Err(e) => error!(log.logger, "\tfailed to save ready check: {}", e; "group" => &check.group)
+    };
+}
+
+/// removes a stored ready check that completed or ran out of time.
+fn remove_ready_check (data: &RwLock<ShareMap>, check: &ReadyCheck) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let id = match check.id {
+        Some(i) => i,
+        None => return
+    };
+    match database.remove_ready_check(id) {
+        Ok (0) => (),
+        Ok (r) => warn!(log.logger, "\tunexpected result removing ready check: {}", r; "group" => &check.group),
+        Err(e) => error!(log.logger, "\tfailed to remove ready check: {}", e; "group" => &check.group)
+    };
+}
+
+/// marks a player as ready in the ready check they are part of. the match is
+/// formed once every player of the ready check is ready.
+///
+/// # Example
+///
+/// ```
+/// lobby::player_ready(&context.http, &context.data, *message.author.id.as_u64()).unwrap();
+/// ```
+pub fn player_ready (http: &Http, data: &RwLock<ShareMap>, player: u64) -> Result<(), Box<dyn Error>> {
+    let checks = data.read().get::<ReadyChecks>().cloned().unwrap();
+    let complete = {
+        let mut checks = checks.lock();
+        match checks.find(player) {
+            Some(c) => {
+                if c.ready(player) {
+                    save_ready_check(data, c);
+                }
+            },
+            None => return Err("you are not part of a ready check.".into())
+        };
+        checks.take_complete()
+    };
+    for check in complete.into_iter() {
+        remove_ready_check(data, &check);
+        start_match(http, data, ChannelId(check.channel), &check.group, check.players());
+    }
+    Ok (())
+}
+
+/// handles a reaction added to a message. the ready emoji on a ready check
+/// marks the player as ready, the ready emoji on a subscription warning keeps
+/// the player subscribed and a numbered emoji on a map vote counts as a vote
+/// for that map. reactions to other messages are ignored.
+pub fn reaction_added (http: &Http, data: &RwLock<ShareMap>, message: u64, player: u64, emoji: &ReactionType) {
+    let emoji = match emoji {
+        ReactionType::Unicode(e) => e,
+        _ => return
+    };
+    if emoji == READY_EMOJI {
+        let checks = data.read().get::<ReadyChecks>().cloned().unwrap();
+        let is_player = match checks.lock().find_by_message(message) {
+            Some(c) => c.contains(player),
+            None => false
+        };
+        if is_player {
+            let _ = player_ready(http, data, player);
+        }
+        let expiries = data.read().get::<Expiries>().cloned().unwrap();
+        let warned = expiries.lock().find_by_message(message, player).is_some();
+        if warned {
+            still_here(http, data, player);
+        }
+    } else if let Some(index) = MAP_EMOJIS.iter().position(|e| e == emoji) {
+        let votes = data.read().get::<MapVotes>().cloned().unwrap();
+        if let Some(v) = votes.lock().find_by_message(message) {
+            if v.vote_index(player, index).is_ok() {
+                save_map_vote(data, v);
+            }
+        };
+    }
+}
+
+/// records the vote of a player for a map in the map vote they are part of.
+///
+/// # Example
+///
+/// ```
+/// lobby::vote_map(&context.data, *message.author.id.as_u64(), "supply").unwrap();
+/// ```
+pub fn vote_map (data: &RwLock<ShareMap>, player: u64, map: &str) -> Result<(), Box<dyn Error>> {
+    let votes = data.read().get::<MapVotes>().cloned().unwrap();
+    let mut votes = votes.lock();
+    let vote = match votes.find(player) {
+        Some(v) => v,
+        None => return Err("you are not part of a map vote.".into())
+    };
+    vote.vote(player, map)?;
+    save_map_vote(data, vote);
+    Ok (())
+}
+
+/// returns whether or not the specified player is in a ready check, map vote
+/// or draft, in which case they may not queue again.
+pub fn is_busy (data: &RwLock<ShareMap>, player: u64) -> bool {
+    let checks = data.read().get::<ReadyChecks>().cloned().unwrap();
+    let votes = data.read().get::<MapVotes>().cloned().unwrap();
+    let drafts = data.read().get::<Drafts>().cloned().unwrap();
+    let busy = checks.lock().find(player).is_some()
+        || votes.lock().find(player).is_some()
+        || drafts.lock().find(player).is_some();
+    busy
+}
+
+/// picks up the match lifecycle restored from the database after a restart.
+/// ready checks, map votes and drafts are given at least a short grace
+/// period and posted again, since players could not act on the old messages
+/// while the bot was down, and matches still being played are announced in
+/// their channels.
+///
+/// # Example
+///
+/// ```
+/// lobby::restored(&client.cache_and_http.http, &client.data);
+/// ```
+pub fn restored (http: &Http, data: &RwLock<ShareMap>) {
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let checks = data.read().get::<ReadyChecks>().cloned().unwrap();
+    let votes = data.read().get::<MapVotes>().cloned().unwrap();
+    let drafts = data.read().get::<Drafts>().cloned().unwrap();
+    let matches = data.read().get::<Matches>().cloned().unwrap();
+    let deadline = SystemTime::now() + RESTORE_GRACE;
+    for check in checks.lock().all_mut().iter_mut() {
+        info!(log.logger, "\tready check restored"; "group" => &check.group);
+        check.deadline = check.deadline.max(deadline);
+        let reply = describe_ready_check(check, "the bot restarted while this ready check was running.");
+        if let Some(m) = post_ready_check(http, data, ChannelId(check.channel), &check.group, &reply) {
+            check.message = Some(m);
+        }
+        save_ready_check(data, check);
+    }
+    for vote in votes.lock().all_mut().iter_mut() {
+        info!(log.logger, "\tmap vote restored"; "group" => &vote.group);
+        vote.deadline = vote.deadline.max(deadline);
+        let reply = describe_map_vote(vote, "the bot restarted while this map vote was running. votes already cast still count.");
+        if let Some(m) = post_map_vote(http, data, ChannelId(vote.channel), &vote.group, &reply, vote.maps().len()) {
+            vote.message = Some(m);
+        }
+        save_map_vote(data, vote);
+    }
+    for draft in drafts.lock().all_mut().iter_mut() {
+        info!(log.logger, "\tdraft restored"; "group" => &draft.group);
+        draft.deadline = draft.deadline.max(deadline);
+        save_draft(data, draft);
+        let reply = format!(
+            "the bot restarted while the `{}` draft was running. pick players with `!pick @player`.\n{}",
+            draft.group, describe_draft(draft)
+        );
+        let _ = ChannelId(draft.channel).say(http, &reply);
+    }
+    for game in matches.lock().all().iter() {
+        info!(log.logger, "\tmatch restored"; "match" => game.id, "group" => &game.group);
+        let reply = format!(
+            "match #{} (`{}`) is still in progress. report the result with `!report win|loss|draw` once it is over.",
+            game.id, game.group
+        );
+        let _ = ChannelId(game.channel).say(http, &reply);
+    }
+}
+
+/// drives the timed phases of the match lifecycle. this is called
+/// periodically by the timer thread.
+pub fn tick (http: &Http, data: &RwLock<ShareMap>) {
+    let checks = data.read().get::<ReadyChecks>().cloned().unwrap();
+    let expired = checks.lock().take_expired(SystemTime::now());
+    for check in expired.into_iter() {
+        remove_ready_check(data, &check);
+        ready_check_failed(http, data, &check);
+    }
+    map_vote_tick(http, data);
+    draft_tick(http, data);
+    expiry_tick(http, data);
+}
+
+/// unsubscribes warned players that did not answer in time, and periodically
+/// warns players that have been waiting in a queue for longer than its time
+/// to live.
+fn expiry_tick (http: &Http, data: &RwLock<ShareMap>) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let queues = data.read().get::<Queues>().cloned().unwrap();
+    let expiries = data.read().get::<Expiries>().cloned().unwrap();
+    let now = SystemTime::now();
+    let expired = expiries.lock().take_expired(now);
+    for warning in expired.iter() {
+        subscription_expired(http, data, warning);
+    }
+    if !expiries.lock().sweep_due(now) {
+        return;
+    }
+    let groups = queues.lock().groups();
+    let mut stale: Vec<(u64, Vec<String>)> = Vec::new();
+    for group in groups.iter() {
+        if expiries.lock().ttl(group).is_none() {
+            continue;
+        }
+        let subscriptions = match database.get_mm_subscriptions(group) {
+            Ok (s) => s,
+            Err(e) => {
+                error!(log.logger, "\tfailed to get subscriptions: {}", e; "group" => group);
+                continue;
+            }
+        };
+        for subscription in subscriptions.iter() {
+            if !expiries.lock().is_stale(group, subscription.discord_uuid, subscription.subscribed_at, now) {
+                continue;
+            }
+            match stale.iter_mut().find(|(p, _)| *p == subscription.discord_uuid) {
+                Some((_, groups)) => groups.push(group.clone()),
+                None => stale.push((subscription.discord_uuid, vec![group.clone()]))
+            };
+        }
+    }
+    for (player, groups) in stale.iter() {
+        warn_subscriber(http, data, *player, groups, Reason::Stale);
+    }
+}
+
+/// handles a player going offline on discord. a player waiting in any queue
+/// is asked whether they are still there, and unsubscribed if they do not
+/// answer in time.
+pub fn went_offline (http: &Http, data: &RwLock<ShareMap>, player: u64) {
+    let queues = data.read().get::<Queues>().cloned().unwrap();
+    let groups = queues.lock().subscriptions(player);
+    warn_subscriber(http, data, player, &groups, Reason::Offline);
+}
+
+/// asks a subscribed player by direct message whether they are still there.
+/// the player keeps their subscriptions to the specified match making groups
+/// by reacting to the message with the ready emoji before the grace period
+/// runs out.
+fn warn_subscriber (http: &Http, data: &RwLock<ShareMap>, player: u64, groups: &[String], reason: Reason) {
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let expiries = data.read().get::<Expiries>().cloned().unwrap();
+    let mut expiries = expiries.lock();
+    let grace = expiries.grace;
+    let warning = match expiries.warn(player, groups, reason, SystemTime::now()) {
+        Some(w) => w,
+        None => return
+    };
+    info!(log.logger, "\twarning subscriber"; "player" => player, "groups" => groups.join(" "));
+    let queues: Vec<String> = groups.iter()
+        .map(|g| format!("`{}`", g))
+        .collect();
+    let reply = format!(
+        "{} {}. react with {} within {} to stay subscribed, otherwise you will be unsubscribed.",
+        match reason {
+            Reason::Stale => "you have been waiting in a match making queue for a while:",
+            Reason::Offline => "you went offline while waiting in a match making queue:"
+        },
+        queues.join(", "), READY_EMOJI, penalties::format_duration(grace)
+    );
+    let sent = UserId(player).create_dm_channel(http)
+        .and_then(|c| c.say(http, &reply));
+    match sent {
+        Ok (m) => {
+            let _ = m.channel_id.create_reaction(http, m.id, ReactionType::Unicode(READY_EMOJI.to_string()));
+            warning.message = Some(*m.id.as_u64());
+        },
+        Err(e) => warn!(log.logger, "\tfailed to send subscription warning: {}", e; "player" => player)
+    };
+}
+
+/// keeps a warned player subscribed and restarts the time to live of their
+/// subscriptions.
+fn still_here (http: &Http, data: &RwLock<ShareMap>, player: u64) {
+    let expiries = data.read().get::<Expiries>().cloned().unwrap();
+    let warning = match expiries.lock().refresh(player, SystemTime::now()) {
+        Some(w) => w,
+        None => return
+    };
+    if let Ok (c) = UserId(player).create_dm_channel(http) {
+        let _ = c.say(http, format!("thanks, you are still subscribed to `{}`.", warning.groups.join("`, `")));
+    }
+}
+
+/// unsubscribes a warned player that did not answer in time from the match
+/// making groups they were warned about and are still waiting in.
+fn subscription_expired (http: &Http, data: &RwLock<ShareMap>, warning: &Warning) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let queues = data.read().get::<Queues>().cloned().unwrap();
+    let mut removed = Vec::new();
+    for group in warning.groups.iter() {
+        if !queues.lock().unsubscribe(group, warning.player) {
+            continue;
+        }
+        match database.remove_mm_user(warning.player, group) {
+            Ok (0) => (),
+            Ok (r) => warn!(log.logger, "\tunexpected result unsubscribing expired player: {}", r; "player" => warning.player),
+            Err(e) => error!(log.logger, "\tfailed to unsubscribe expired player: {}", e; "player" => warning.player)
+        };
+        removed.push(format!("`{}`", group));
+    }
+    if removed.is_empty() {
+        return;
+    }
+    info!(log.logger, "\tsubscription expired"; "player" => warning.player, "groups" => removed.len());
+    if let Ok (c) = UserId(warning.player).create_dm_channel(http) {
+        let _ = c.say(http, format!(
+            "you have been unsubscribed from {} {}. type `!subscribe` to queue again.",
+            removed.join(", "),
+            match warning.reason {
+                Reason::Stale => "after waiting without saying you are still there",
+                Reason::Offline => "after going offline"
+            }
+        ));
+    }
+}
+
+/// handles a ready check that ran out of time. players that did not ready up
+/// are banned from queueing, and the remaining players are put back into the
+/// queue at their original positions.
+fn ready_check_failed (http: &Http, data: &RwLock<ShareMap>, check: &ReadyCheck) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let queues = data.read().get::<Queues>().cloned().unwrap();
+    let channel = ChannelId(check.channel);
+    let missing = check.missing_players();
+    let ready = check.ready_players();
+    info!(log.logger, "\tready check failed"; "group" => &check.group, "missing" => missing.len());
+    let expiries = data.read().get::<Expiries>().cloned().unwrap();
+    let now = SystemTime::now();
+    for player in ready.iter() {
+        // readying up shows the player is still there, so their subscription
+        // does not go stale for having waited since before the pop
+        expiries.lock().refresh(*player, now);
+        match database.requeue_mm_user(*player, &check.group) {
+            Ok (0) | Ok (3) => (),
+            Ok (r) => warn!(log.logger, "\tunexpected result requeueing player: {}", r; "player" => player),
+            Err(e) => error!(log.logger, "\tfailed to requeue player: {}", e; "player" => player)
+        };
+    }
+    let popped = queues.lock().requeue(&check.group, &ready);
+    let bans: Vec<String> = missing.iter()
+        .map(|p| match penalize(http, data, *p, Offence::MissedReadyCheck) {
+            Some(ban) => format!("{} ({})", mention(*p), penalties::format_duration(ban)),
+            None => mention(*p)
+        })
+        .collect();
+    let _ = channel.say(http, format!(
+        "the `{}` ready check failed. {} did not ready up in time and cannot queue for a while. everyone else has been put back into the queue.",
+        check.group, bans.join(" ")
+    ));
+    match popped {
+        Ok (Some(players)) => queue_popped(http, data, channel, &check.group, &players),
+        Ok (None) => (),
+        Err(e) => error!(log.logger, "\tfailed to requeue players: {}", e; "group" => &check.group)
+    };
+}
+
+/// bans a player from queueing for an offence. the ban escalates with the
+/// offences the player committed recently, and the player is told how long
+/// it lasts by direct message. returns the length of the ban, or None if it
+/// could not be stored.
+///
+/// # Example
+///
+/// ```
+/// let ban = lobby::penalize(&context.http, &context.data, player, Offence::AbandonedMatch);
+/// ```
+pub fn penalize (http: &Http, data: &RwLock<ShareMap>, player: u64, offence: Offence) -> Option<Duration> {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let now = SystemTime::now();
+    let previous = match database.get_penalties(player) {
+        Ok (p) => p,
+        Err(e) => {
+            error!(log.logger, "\tfailed to get penalties: {}", e; "player" => player);
+            return None;
+        }
+    };
+    let ban = penalties::ban_duration(&previous, now);
+    match database.add_penalty(player, offence, now + ban) {
+        Ok (0) => info!(log.logger, "\tpenalized player"; "player" => player, "offence" => offence.as_str(), "seconds" => ban.as_secs()),
+        Ok (r) => {
+            warn!(log.logger, "\tunexpected result penalizing player: {}", r; "player" => player);
+            return None;
+        },
+        Err(e) => {
+            error!(log.logger, "\tfailed to penalize player: {}", e; "player" => player);
+            return None;
+        }
+    };
+    let reply = format!("you {} and cannot queue for {}.", offence.describe(), penalties::format_duration(ban));
+    let sent = UserId(player).create_dm_channel(http)
+        .and_then(|c| c.say(http, &reply));
+    if let Err(e) = sent {
+        warn!(log.logger, "\tfailed to send penalty: {}", e; "player" => player);
+    }
+    Some(ban)
+}
+
+/// starts the match of players that passed a ready check. groups with a map
+/// pool vote on a map first, leaving out recently played maps, before their
+/// teams are formed.
+fn start_match (http: &Http, data: &RwLock<ShareMap>, channel: ChannelId, group: &str, players: &[u64]) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let votes = data.read().get::<MapVotes>().cloned().unwrap();
+    let (pool, recent, timeout) = {
+        let votes = votes.lock();
+        (votes.pool(group).map(|p| p.to_vec()), votes.recent, votes.timeout)
+    };
+    let pool = match pool {
+        Some(p) => p,
+        None => {
+            form_teams(http, data, channel, group, players, None);
+            return;
+        }
+    };
+    let recent = match database.get_recent_maps(group, recent) {
+        Ok (r) => r,
+        Err(e) => {
+            error!(log.logger, "\tfailed to get recently played maps: {}", e; "group" => group);
+            Vec::new()
+        }
+    };
+    let maps = map_vote::candidates(&pool, &recent);
+    if maps.len() == 1 {
+        form_teams(http, data, channel, group, players, Some(maps[0].clone()));
+        return;
+    }
+    let vote = MapVote::construct(group, *channel.as_u64(), players, &maps, timeout);
+    let reply = describe_map_vote(&vote, "every player is ready!");
+    // the map vote is added before it is posted, so its players count as busy
+    // straight away without the lock being held while discord answers
+    votes.lock().add(vote);
+    let message = post_map_vote(http, data, channel, group, &reply, maps.len());
+    let mut votes = votes.lock();
+    if let Some(v) = votes.find(players[0]) {
+        v.message = message;
+        save_map_vote(data, v);
+    }
+}
+
+/// formats a map vote with the emoji to react with for each map.
+fn describe_map_vote (vote: &MapVote, headline: &str) -> String {
+    let options: Vec<String> = vote.maps().iter()
+        .enumerate()
+        .map(|(i, m)| match MAP_EMOJIS.get(i) {
+            Some(e) => format!("{} `{}`", e, m),
+            None => format!("`{}`", m)
+        })
+        .collect();
+    format!(
+        "{} vote for the map with `!vote <map>` or a reaction within {} seconds.\n{}",
+        headline, seconds_left(vote.deadline), options.join("\n")
+    )
+}
+
+/// posts a map vote in a channel with a reaction for each of its maps, and
+/// returns the id of the message it was posted as.
+fn post_map_vote (http: &Http, data: &RwLock<ShareMap>, channel: ChannelId, group: &str, reply: &str, maps: usize) -> Option<u64> {
+    let log = data.read().get::<Log>().cloned().unwrap();
+    match channel.say(http, reply) {
+        Ok (m) => {
+            for emoji in MAP_EMOJIS.iter().take(maps) {
+                let _ = channel.create_reaction(http, m.id, ReactionType::Unicode(emoji.to_string()));
+            }
+            Some(*m.id.as_u64())
+        },
+        Err(e) => {
+            error!(log.logger, "\tfailed to post map vote: {}", e; "group" => group);
+            None
+        }
+    }
+}
+
+/// stores a map vote so it survives a restart of the bot, recording the
+/// database id of a new one.
+fn save_map_vote (data: &RwLock<ShareMap>, vote: &mut MapVote) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    match database.save_map_vote(vote) {
+        Ok (id) => vote.id = Some(id),
+        Err(e) => error!(log.logger, "\tfailed to save map vote: {}", e; "group" => &vote.group)
+    };
+}
+
+/// removes a stored map vote that is over.
+fn remove_map_vote (data: &RwLock<ShareMap>, vote: &MapVote) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let id = match vote.id {
+        Some(i) => i,
+        None => return
+    };
+    match database.remove_map_vote(id) {
+        Ok (0) => (),
+        Ok (r) => warn!(log.logger, "\tunexpected result removing map vote: {}", r; "group" => &vote.group),
+        Err(e) => error!(log.logger, "\tfailed to remove map vote: {}", e; "group" => &vote.group)
+    };
+}
+
+/// closes every map vote that is over and forms the teams of its match.
+fn map_vote_tick (http: &Http, data: &RwLock<ShareMap>) {
+    let votes = data.read().get::<MapVotes>().cloned().unwrap();
+    let finished = votes.lock().take_finished(SystemTime::now());
+    for vote in finished.into_iter() {
+        remove_map_vote(data, &vote);
+        let map = vote.winner();
+        let channel = ChannelId(vote.channel);
+        let _ = channel.say(http, format!("the `{}` match will be played on **{}**.", vote.group, map));
+        form_teams(http, data, channel, &vote.group, vote.players(), Some(map));
+    }
+}
+
+/// forms the teams of a match. groups configured for drafting start a
+/// captain draft, every other group is balanced by rating.
+fn form_teams (http: &Http, data: &RwLock<ShareMap>, channel: ChannelId, group: &str, players: &[u64], map: Option<String>) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let drafts = data.read().get::<Drafts>().cloned().unwrap();
+    let rated = match database.get_mm_ratings(group, players) {
+        Ok (r) => r,
+        Err(e) => {
+            error!(log.logger, "\tfailed to get player ratings, using defaults: {}", e; "group" => group);
+            players.iter()
+                .map(|p| RatedPlayer { discord_uuid: *p, rating: rating::DEFAULT_RATING })
+                .collect()
+        }
+    };
+    let mut drafts = drafts.lock();
+    let selection = match drafts.selection(group) {
+        TeamSelection::Balance => {
+            drop(drafts);
+            record_match(http, data, channel, group, balance::balance(&rated), map);
+            return;
+        },
+        TeamSelection::Draft(s) => s
+    };
+    let captains = draft::choose_captains(&rated, selection);
+    let mut draft = Draft::construct(group, *channel.as_u64(), &rated, captains, drafts.timeout);
+    draft.map = map;
+    if draft.is_complete() {
+        drop(drafts);
+        record_match(http, data, channel, group, draft.teams, draft.map);
+        return;
+    }
+    let reply = format!(
+        "{} and {} are captains for the `{}` draft. pick players with `!pick @player`.\n{}",
+        mention(captains.0.discord_uuid), mention(captains.1.discord_uuid), group, describe_draft(&draft)
+    );
+    save_draft(data, &mut draft);
+    drafts.add(draft);
+    drop(drafts);
+    if let Err(e) = channel.say(http, &reply) {
+        error!(log.logger, "\tfailed to announce draft: {}", e; "group" => group);
+    }
+}
+
+/// picks a player for the team of the specified captain in the draft they
+/// are leading. the match is formed once every player has been picked.
+///
+/// # Example
+///
+/// ```
+/// lobby::draft_pick(&context.http, &context.data, *message.author.id.as_u64(), *player.as_u64()).unwrap();
+/// ```
+pub fn draft_pick (http: &Http, data: &RwLock<ShareMap>, captain: u64, player: u64) -> Result<(), Box<dyn Error>> {
+    let drafts = data.read().get::<Drafts>().cloned().unwrap();
+    let mut drafts = drafts.lock();
+    let draft = match drafts.find(captain) {
+        Some(d) => d,
+        None => return Err("you are not part of a draft.".into())
+    };
+    draft.pick(captain, player)?;
+    if !draft.is_complete() {
+        save_draft(data, draft);
+    }
+    let channel = ChannelId(draft.channel);
+    let reply = format!("{} picked {}.\n{}", mention(captain), mention(player), describe_draft(draft));
+    let complete = drafts.take_complete();
+    drop(drafts);
+    let _ = channel.say(http, &reply);
+    for draft in complete.into_iter() {
+        remove_draft(data, &draft);
+        record_match(http, data, ChannelId(draft.channel), &draft.group, draft.teams, draft.map);
+    }
+    Ok (())
+}
+
+/// makes automatic picks for every draft captain that ran out of time and
+/// forms the match of every draft that completed.
+fn draft_tick (http: &Http, data: &RwLock<ShareMap>) {
+    let drafts = data.read().get::<Drafts>().cloned().unwrap();
+    let mut drafts = drafts.lock();
+    let picks = drafts.auto_pick_expired(SystemTime::now());
+    let complete = drafts.take_complete();
+    for (_, captain, _) in picks.iter() {
+        if let Some(d) = drafts.find(*captain) {
+            save_draft(data, d);
+        }
+    }
+    drop(drafts);
+    for (channel, captain, player) in picks.iter() {
+        let _ = ChannelId(*channel).say(http, format!(
+            "{} ran out of time and was given {}.", mention(*captain), mention(player.discord_uuid)
+        ));
+    }
+    for draft in complete.into_iter() {
+        remove_draft(data, &draft);
+        record_match(http, data, ChannelId(draft.channel), &draft.group, draft.teams, draft.map);
+    }
+}
+
+/// stores a captain draft so it survives a restart of the bot, recording the
+/// database id of a new one.
+fn save_draft (data: &RwLock<ShareMap>, draft: &mut Draft) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    match database.save_draft(draft) {
+        Ok (id) => draft.id = Some(id),
+        Err(e) => error!(log.logger, "\tfailed to save draft: {}", e; "group" => &draft.group)
+    };
+}
+
+/// removes a stored captain draft that completed.
+fn remove_draft (data: &RwLock<ShareMap>, draft: &Draft) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let id = match draft.id {
+        Some(i) => i,
+        None => return
+    };
+    match database.remove_draft(id) {
+        Ok (0) => (),
+        Ok (r) => warn!(log.logger, "\tunexpected result removing draft: {}", r; "group" => &draft.group),
+        Err(e) => error!(log.logger, "\tfailed to remove draft: {}", e; "group" => &draft.group)
+    };
+}
+
+/// stores whether a match went live and the result each team reported, so
+/// they survive a restart of the bot.
+///
+/// # Example
+///
+/// ```
+/// lobby::save_match_state(&context.data, game);
+/// ```
+pub fn save_match_state (data: &RwLock<ShareMap>, game: &Match) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    match database.save_match_state(game) {
+        Ok (0) => (),
+        Ok (r) => warn!(log.logger, "\tunexpected result saving match state: {}", r; "match" => game.id),
+        Err(e) => error!(log.logger, "\tfailed to save match state: {}", e; "match" => game.id)
+    };
+}
+
+/// records a match with its final teams and map and announces it in the
+/// specified channel. this is the point where balanced and drafted matches
+/// share the same lifecycle.
+fn record_match (http: &Http, data: &RwLock<ShareMap>, channel: ChannelId, group: &str, teams: Teams, map: Option<String>) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let matches = data.read().get::<Matches>().cloned().unwrap();
+    let match_id = match database.add_match(group, &teams, map.as_deref(), *channel.as_u64()) {
+        Ok (m) => m,
+        Err(e) => {
+            error!(log.logger, "\tfailed to record match: {}", e; "group" => group);
+            let _ = channel.say(http, format!("the teams of the `{}` match are set but the match could not be recorded.", group));
+            return;
+        }
+    };
+    let on_map = match &map {
+        Some(m) => format!(" on **{}**", m),
+        None => String::new()
+    };
+    let players: Vec<u64> = teams.axis.iter()
+        .chain(teams.allies.iter())
+        .map(|p| p.discord_uuid)
+        .collect();
+    let server = allocate_server(http, data, match_id, &players, map.as_deref());
+    let on_server = match &server {
+        Some(s) => format!("server `{}` ({}) is ready, connection details have been sent to every player.", s.name, s.region),
+        None => "no game server is free, so the teams will have to find a server themselves.".to_string()
+    };
+    let reply = format!(
+        "match #{} (`{}`) is on{}!\n{}\n{}\nreport the result with `!report win|loss|draw` when the match is over.",
+        match_id, group, on_map, describe_teams(&teams), on_server
+    );
+    let mut game = Match::construct(match_id, group, *channel.as_u64(), teams);
+    game.server = server.map(|s| s.name);
+    matches.lock().add(game);
+    if let Err(e) = channel.say(http, &reply) {
+        error!(log.logger, "\tfailed to announce match: {}", e; "group" => group);
+    }
+}
+
+/// allocates the first free and empty game server to a match. the server is
+/// claimed in the database before a status query verifies nobody is playing
+/// on it, then it is locked with a random password and changed to the map of
+/// the match over rcon. every player is sent the connect string by direct
+/// message. returns the allocated server, or none if no server was usable.
+fn allocate_server (http: &Http, data: &RwLock<ShareMap>, match_id: i32, players: &[u64], map: Option<&str>) -> Option<Server> {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let servers = data.read().get::<Servers>().cloned().unwrap();
+    for server in servers.all().iter() {
+        match database.allocate_server(&server.name, match_id) {
+            Ok (0) => (),
+            Ok (2) => continue,
+            Ok (r) => {
+                warn!(log.logger, "\tunexpected result allocating server: {}", r; "server" => &server.name);
+                continue;
+            },
+            Err(e) => {
+                error!(log.logger, "\tfailed to allocate server: {}", e; "server" => &server.name);
+                continue;
+            }
+        };
+        let password = servers::generate_password();
+        if let Err(e) = prepare_server(server, &password, map) {
+            warn!(log.logger, "\tskipping server: {}", e; "server" => &server.name, "match" => match_id);
+            release_server(data, match_id);
+            continue;
+        }
+        info!(log.logger, "\tallocated server"; "server" => &server.name, "match" => match_id);
+        let reply = format!(
+            "match #{} is ready on server `{}`. paste this into your ET console to join:\n`{}`",
+            match_id, server.name, server.connect_string(&password)
+        );
+        for player in players.iter() {
+            let sent = UserId(*player).create_dm_channel(http)
+                .and_then(|c| c.say(http, &reply));
+            if let Err(e) = sent {
+                warn!(log.logger, "\tfailed to send connect string: {}", e; "player" => player);
+            }
+        }
+        return Some(server.clone());
+    }
+    warn!(log.logger, "\tno free server for match"; "match" => match_id);
+    None
+}
+
+/// verifies that nobody is playing on a server, then sets its password and
+/// changes it to the specified map.
+fn prepare_server (server: &Server, password: &str, map: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let status = query::status(&server.address(), QUERY_TIMEOUT)?;
+    if !status.is_empty() {
+        return Err(format!("{} players are on the server", status.players.len()).into());
+    }
+    let rcon = Rcon::construct(&server.address(), &server.rcon_password, RCON_TIMEOUT)?;
+    rcon.send(&format!("set g_password {}", password))?;
+    if let Some(m) = map {
+        rcon.send(&format!("map {}", m))?;
+    }
+    Ok (())
+}
+
+/// releases the game server allocated to a match, if there is one.
+fn release_server (data: &RwLock<ShareMap>, match_id: i32) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    match database.release_server(match_id) {
+        Ok (0) => info!(log.logger, "\treleased server"; "match" => match_id),
+        Ok (1) => (),
+        Ok (r) => warn!(log.logger, "\tunexpected result releasing server: {}", r; "match" => match_id),
+        Err(e) => error!(log.logger, "\tfailed to release server: {}", e; "match" => match_id)
+    };
+}
+
+/// handles an event read from the log of a game server, before the event is
+/// applied to the tracked clients. events involving players add to the
+/// statistics of the match being played on the server. the match goes live
+/// once the map restarts with players connected at the end of warmup, and
+/// players that disconnect without coming back are banned once the match is
+/// over. the match completes with the result of its final round once the map
+/// shuts down and the final statistics have been logged, so players do not
+/// have to report it. stopwatch maps only complete after their second round.
+/// the match stays active until the database has recorded its result, so it
+/// can still be reported if that failed. chat messages containing a link
+/// token link the identity of the player that sent them.
+pub fn server_event (http: &Http, data: &RwLock<ShareMap>, server: &str, clients: &Clients, event: &Event) {
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let matches = data.read().get::<Matches>().cloned().unwrap();
+    if let Event::Say { name, text } = event {
+        link_identity(http, data, server, clients, name, text);
+        return;
+    }
+    let (match_id, channel, result) = {
+        let mut matches = matches.lock();
+        let game = match matches.find_by_server(server) {
+            Some(g) => g,
+            None => return
+        };
+        match event {
+            Event::InitGame(_) => {
+                if !game.live && !clients.is_empty() {
+                    game.live = true;
+                    save_match_state(data, game);
+                }
+            },
+            Event::ClientDisconnect(slot) => {
+                if let Some(identity) = clients.get(*slot).and_then(Identity::from_userinfo) {
+                    game.depart(identity);
+                }
+            },
+            Event::ClientUserinfoChanged { info, .. } => {
+                if let Some(identity) = Identity::from_userinfo(info) {
+                    game.rejoin(&identity);
+                }
+            },
+            Event::Exit { .. } | Event::ShutdownGame => (),
+            _ => game.stats.record(event, clients)
+        };
+        match game.follow_round(event) {
+            Some(r) => (game.id, ChannelId(game.channel), r),
+            None => return
+        }
+    };
+    info!(log.logger, "\tmatch result read from server log"; "server" => server, "match" => match_id);
+    if let Err(e) = complete_match(http, data, channel, match_id, result) {
+        error!(log.logger, "\tfailed to complete match from server log: {}", e; "match" => match_id);
+        return;
+    }
+    let finished = matches.lock().remove(match_id);
+    if let Some(game) = &finished {
+        record_stats(data, game);
+        penalize_departures(http, data, game);
+    }
+}
+
+/// stores the statistics of every player of a match that has linked their
+/// in-game identity. statistics of players that have not are dropped.
+pub fn record_stats (data: &RwLock<ShareMap>, game: &Match) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    for (identity, stats) in game.stats.players().iter() {
+        match database.add_player_stats(game.id, identity, stats) {
+            Ok (0) => (),
+            Ok (2) => info!(log.logger, "\tdropped statistics of unlinked player"; "match" => game.id, "identity" => identity.value()),
+            Ok (r) => warn!(log.logger, "\tunexpected result recording statistics: {}", r; "match" => game.id),
+            Err(e) => error!(log.logger, "\tfailed to record statistics: {}", e; "match" => game.id)
+        };
+    }
+}
+
+/// bans every player of a match that left its game server and did not come
+/// back, unless enough opponents already reported them for abandoning it.
+/// departures of identities that are not linked to a player of the match are
+/// ignored.
+pub fn penalize_departures (http: &Http, data: &RwLock<ShareMap>, game: &Match) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    for (identity, offence) in game.departures().iter() {
+        let player = match database.get_linked_user(identity) {
+            Ok (Some(p)) => p,
+            Ok (None) => continue,
+            Err(e) => {
+                error!(log.logger, "\tfailed to get linked user: {}", e; "identity" => identity.value());
+                continue;
+            }
+        };
+        if game.team_of(player).is_some() && !game.reported_abandoned(player) {
+            penalize(http, data, player, *offence);
+        }
+    }
+}
+
+/// links the identity of the player that said a link token in the chat of a
+/// game server to the user the token was issued to, who is told the outcome
+/// by direct message.
+fn link_identity (http: &Http, data: &RwLock<ShareMap>, server: &str, clients: &Clients, name: &str, text: &str) {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let links = data.read().get::<Links>().cloned().unwrap();
+    let user = match links.lock().redeem(text, SystemTime::now()) {
+        Some(u) => u,
+        None => return
+    };
+    let identity = match clients.find_by_name(name) {
+        Some((_, info)) => Identity::from_userinfo(info),
+        None => Identity::from_name(name)
+    };
+    let reply = match identity {
+        Some(i) => match database.link_user_identity(user, &i) {
+            Ok (0) => {
+                info!(log.logger, "\tlinked identity"; "user" => user, "kind" => i.kind(), "server" => server);
+                format!("your discord account is now linked to the {} `{}`.", i.kind(), i.value())
+            },
+            Ok (2) => format!("the {} `{}` is already linked to another discord account.", i.kind(), i.value()),
+            Ok (3) => format!("the {} `{}` is already linked to your discord account.", i.kind(), i.value()),
+            Ok (r) => {
+                warn!(log.logger, "\tunexpected result linking identity: {}", r; "user" => user);
+                "your identity could not be linked, please try again.".to_string()
+            },
+            Err(e) => {
+                error!(log.logger, "\tfailed to link identity: {}", e; "user" => user);
+                "your identity could not be linked, please try again.".to_string()
+            }
+        },
+        None => "your in-game identity could not be recognised, please set a name and try again.".to_string()
+    };
+    let sent = UserId(user).create_dm_channel(http)
+        .and_then(|c| c.say(http, &reply));
+    if let Err(e) = sent {
+        warn!(log.logger, "\tfailed to send link result: {}", e; "user" => user);
+    }
+}
+
+/// completes a match with the specified result. the ratings of every player
+/// are updated from their current ratings, the result is stored, the game
+/// server of the match is released and the rating changes are announced in
+/// the specified channel.
+///
+/// # Example
+///
+/// ```
+/// lobby::complete_match(&context.http, &context.data, message.channel_id, match_id, MatchResult::Axis).unwrap();
+/// ```
+pub fn complete_match (http: &Http, data: &RwLock<ShareMap>, channel: ChannelId, match_id: i32, result: MatchResult) -> Result<(), Box<dyn Error>> {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    let teams = database.get_match_players(match_id)?;
+    let updated = rating::update(&teams, result.axis_score());
+    match database.complete_match(match_id, result, &updated)? {
+        0 => {
+            info!(log.logger, "\tmatch completed"; "match" => match_id, "result" => result.as_str());
+            release_server(data, match_id);
+        },
+        1 => return Err(format!("match #{} does not exist.", match_id).into()),
+        2 => {
+            forget_match(data, match_id);
+            return Err(format!("match #{} is already completed or cancelled.", match_id).into());
+        },
+        r => return Err(format!("database returned an unkown result when calling `complete_match()`: `{}`", r).into())
+    };
+    let headline = match result {
+        MatchResult::Draw => format!("match #{} ended in a draw.", match_id),
+        _ => format!("match #{} was won by **{}**.", match_id, result.as_str())
+    };
+    let changes: Vec<String> = teams.axis.iter()
+        .chain(teams.allies.iter())
+        .zip(updated.axis.iter().chain(updated.allies.iter()))
+        .map(|(before, after)| format!("{} {:.0} → {:.0}", mention(before.discord_uuid), before.rating, after.rating))
+        .collect();
+    let _ = channel.say(http, format!("{}\n{}", headline, changes.join("\n")));
+    Ok (())
+}
+
+/// marks a match as disputed, releases its game server and announces that it
+/// has been sent to the admins instead of being applied.
+///
+/// # Example
+///
+/// ```
+/// lobby::dispute_match(&context.http, &context.data, message.channel_id, match_id).unwrap();
+/// ```
+pub fn dispute_match (http: &Http, data: &RwLock<ShareMap>, channel: ChannelId, match_id: i32) -> Result<(), Box<dyn Error>> {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    match database.dispute_match(match_id)? {
+        0 => {
+            warn!(log.logger, "\tmatch result disputed"; "match" => match_id);
+            release_server(data, match_id);
+        },
+        1 => return Err(format!("match #{} does not exist.", match_id).into()),
+        2 => {
+            forget_match(data, match_id);
+            return Err(format!("match #{} is already completed or cancelled.", match_id).into());
+        },
+        r => return Err(format!("database returned an unkown result when calling `dispute_match()`: `{}`", r).into())
+    };
+    let _ = channel.say(http, format!("the teams of match #{} reported conflicting results. the match has been sent to the admins for review.", match_id));
+    Ok (())
+}
+
+/// cancels a match without a result, releases its game server and announces
+/// that the ratings of its players are unchanged.
+///
+/// # Example
+///
+/// ```
+/// lobby::cancel_match(&context.http, &context.data, message.channel_id, match_id).unwrap();
+/// ```
+pub fn cancel_match (http: &Http, data: &RwLock<ShareMap>, channel: ChannelId, match_id: i32) -> Result<(), Box<dyn Error>> {
+    let database = data.read().get::<Database>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    match database.cancel_match(match_id)? {
+        0 => {
+            warn!(log.logger, "\tmatch cancelled"; "match" => match_id);
+            release_server(data, match_id);
+        },
+        1 => return Err(format!("match #{} does not exist.", match_id).into()),
+        2 => {
+            forget_match(data, match_id);
+            return Err(format!("match #{} is already completed or cancelled.", match_id).into());
+        },
+        r => return Err(format!("database returned an unkown result when calling `cancel_match()`: `{}`", r).into())
+    };
+    let _ = channel.say(http, format!("match #{} has been cancelled by an admin. the ratings of its players are unchanged.", match_id));
+    Ok (())
+}
+
+/// removes a match that the database already completed or cancelled from the
+/// active matches, so its players are free to subscribe again. the lock on
+/// the active matches must not be held by the caller.
+fn forget_match (data: &RwLock<ShareMap>, match_id: i32) {
+    let matches = data.read().get::<Matches>().cloned().unwrap();
+    let log = data.read().get::<Log>().cloned().unwrap();
+    if matches.lock().remove(match_id).is_some() {
+        warn!(log.logger, "\tforgot match finished elsewhere"; "match" => match_id);
+    }
+}
+
+/// formats both teams with their average ratings and win chances.
+fn describe_teams (teams: &Teams) -> String {
+    let axis_chance = teams.axis_win_chance() * 100.0;
+    format!(
+        "**axis** ({:.0}, {:.0}% to win): {}\n**allies** ({:.0}, {:.0}% to win): {}",
+        teams.axis_rating(), axis_chance, roster(&teams.axis),
+        teams.allies_rating(), 100.0 - axis_chance, roster(&teams.allies)
+    )
+}
+
+/// formats the teams of a draft so far along with the captain on the clock
+/// and the players still available.
+fn describe_draft (draft: &Draft) -> String {
+    if draft.is_complete() {
+        return "the draft is complete.".to_string();
+    }
+    let pool: Vec<String> = draft.pool().iter()
+        .map(|p| format!("{} ({:.0})", mention(p.discord_uuid), p.rating))
+        .collect();
+    format!(
+        "**axis**: {}\n**allies**: {}\n{} picks for **{}** next ({} seconds). available: {}",
+        roster(&draft.teams.axis), roster(&draft.teams.allies),
+        mention(draft.current_captain()), draft.current_team().as_str(), seconds_left(draft.deadline), pool.join(" ")
+    )
+}
@@ -1,21 +1,71 @@
+pub mod admins;
+pub mod channels;
 mod commands;
+pub mod leaderboards;
+mod lobby;
+mod timer;
 
+use admins:: {
+    Admins,
+    ADMIN_CHECK
+};
+use channels::Channels;
 use commands:: {
+    abandon::*,
+    adjustrating::*,
+    ban::*,
+    cancelmatch::*,
+    disputes::*,
+    endseason::*,
+    forceadd::*,
+    forceremove::*,
+    leaderboard::*,
+    link::*,
+    pick::*,
     ping::*,
+    ready::*,
+    report::*,
+    setresult::*,
+    startseason::*,
+    stats::*,
+    status::*,
     subscribe::*,
-    unsubscribe::*
+    unban::*,
+    unsubscribe::*,
+    vote::*,
+    who::*
 };
 use crate::database::Database;
 use crate::logger::Log;
+use leaderboards::Leaderboards;
+use crate::match_making:: {
+    draft::Drafts,
+    expiry::Expiries,
+    links::Links,
+    map_vote::MapVotes,
+    matches::Matches,
+    queue::Queues,
+    ready_check::ReadyChecks,
+    servers::Servers
+};
 use serenity:: {
     client::bridge::gateway::ShardManager,
     framework:: {
         StandardFramework,
-        standard::macros::group
+        standard:: {
+            DispatchError,
+            Reason,
+            macros::group
+        }
     },
     model:: {
-        event::ResumedEvent,
-        gateway::Ready
+        channel::Reaction,
+        event:: {
+            PresenceUpdateEvent,
+            ResumedEvent
+        },
+        gateway::Ready,
+        user::OnlineStatus
     },
     prelude::*
 };
@@ -25,6 +75,35 @@ use std:: {
     sync::Arc
 };
 
+/// State structure for the match lifecycle state shared with the bot
+///
+/// # Members
+///
+///     ```
+///     queues: match making queues
+///     checks: ready checks of popped queues
+///     drafts: captain drafts in progress
+///     votes: map votes in progress
+///     matches: matches that have not yet completed
+///     expiries: subscriptions that are about to expire
+///     servers: game servers matches are allocated to
+///     leaderboards: leaderboards posted for players to page through
+///     admins: discord roles whose members may use admin commands
+///     channels: match making groups hosted in each configured channel
+///     ```
+pub struct State {
+    pub queues: Queues,
+    pub checks: ReadyChecks,
+    pub drafts: Drafts,
+    pub votes: MapVotes,
+    pub matches: Matches,
+    pub expiries: Expiries,
+    pub servers: Servers,
+    pub leaderboards: Leaderboards,
+    pub admins: Admins,
+    pub channels: Channels
+}
+
 /// Bot structure for discord bot
 ///
 /// # Members
@@ -45,18 +124,44 @@ impl Bot {
     /// ```
     /// let log = Arc::new(logger::Log::new());
     /// let discord_token = "token";
-    /// let mut bot = bot::Bot::construct(&discord_token, &log).unwrap();
+    /// let queues = queue::Queues::construct(&config.group_names(), &config.capacities()).unwrap();
+    /// let checks = ready_check::ReadyChecks::construct(timeout);
+    /// let drafts = draft::Drafts::construct(selections, pick_timeout);
+    /// let votes = map_vote::MapVotes::construct(pools, recent, vote_timeout);
+    /// let matches = matches::Matches::construct();
+    /// let expiries = expiry::Expiries::construct(ttl, ttls, grace);
+    /// let servers = servers::Servers::construct(config.servers.clone());
+    /// let leaderboards = leaderboards::Leaderboards::construct(provisional_games);
+    /// let admins = admins::Admins::construct(&config.admin_roles());
+    /// let channels = channels::Channels::construct(config.channel_groups());
+    /// let state = bot::State { queues, checks, drafts, votes, matches, expiries, servers, leaderboards, admins, channels };
+    /// let mut bot = bot::Bot::construct(&discord_token, &database, state, &log).unwrap();
     /// ```
-    pub fn construct(discord_token: &str, database: &Arc<Database>, log: &Arc<Log>) -> Result<Self, Box<dyn Error>> {
-        let mut client = Client::new(&discord_token, Handler)?;
+    pub fn construct(discord_token: &str, database: &Arc<Database>, state: State, log: &Arc<Log>) -> Result<Self, Box<dyn Error>> {
+        let mut client = Client::new(discord_token, Handler)?;
 
         // pack context data
         {
             let mut data = client.data.write();
-            data.insert::<Database>(Arc::clone(&database));
-            data.insert::<Log>(Arc::clone(&log));
+            data.insert::<Database>(Arc::clone(database));
+            data.insert::<Log>(Arc::clone(log));
+            data.insert::<Queues>(Arc::new(Mutex::new(state.queues)));
+            data.insert::<Matches>(Arc::new(Mutex::new(state.matches)));
+            data.insert::<Links>(Arc::new(Mutex::new(Links::construct())));
+            data.insert::<ReadyChecks>(Arc::new(Mutex::new(state.checks)));
+            data.insert::<Drafts>(Arc::new(Mutex::new(state.drafts)));
+            data.insert::<MapVotes>(Arc::new(Mutex::new(state.votes)));
+            data.insert::<Expiries>(Arc::new(Mutex::new(state.expiries)));
+            data.insert::<Servers>(Arc::new(state.servers));
+            data.insert::<Leaderboards>(Arc::new(Mutex::new(state.leaderboards)));
+            data.insert::<Admins>(Arc::new(state.admins));
+            data.insert::<Channels>(Arc::new(state.channels));
             data.insert::<ShardManagerContainer>(Arc::clone(&client.shard_manager));
         }
+        // pick up the match lifecycle restored from the database
+        lobby::restored(&client.cache_and_http.http, &client.data);
+        // start match lifecycle timer
+        timer::spawn(Arc::clone(&client.cache_and_http.http), Arc::clone(&client.data));
         // set owners
         let owners = match client.cache_and_http.http.get_current_application_info() {
             Ok(o) => {
@@ -73,6 +178,13 @@ impl Bot {
                 .prefix("!")
             )
             .group(&GENERAL_GROUP)
+            .group(&ADMIN_GROUP)
+            // tell users why a check stopped their command
+            .on_dispatch_error(|context, message, error| {
+                if let DispatchError::CheckFailed(_, Reason::User(reply)) = error {
+                    let _ = message.channel_id.say(&context.http, &reply);
+                }
+            })
             // handle command errors
             .after(|context, message, command, result| {
                 if let Err(e) = result {
@@ -103,6 +215,34 @@ impl EventHandler for Handler {
         let log = context.data.read().get::<Log>().cloned().unwrap();
         info!(log.logger, "\t{} connected to discord...", ready.user.name);
     }
+    // handle reaction add event, ignoring the reactions the bot adds itself
+    fn reaction_add(&self, context: Context, reaction: Reaction) {
+        if reaction.user_id == context.cache.read().user.id {
+            return;
+        }
+        leaderboards::reaction_toggled(&context.http, &context.data, *reaction.message_id.as_u64(), &reaction.emoji);
+        lobby::reaction_added(
+            &context.http,
+            &context.data,
+            *reaction.message_id.as_u64(),
+            *reaction.user_id.as_u64(),
+            &reaction.emoji
+        );
+    }
+    // handle reaction remove event
+    fn reaction_remove(&self, context: Context, reaction: Reaction) {
+        if reaction.user_id == context.cache.read().user.id {
+            return;
+        }
+        leaderboards::reaction_toggled(&context.http, &context.data, *reaction.message_id.as_u64(), &reaction.emoji);
+    }
+    // handle presence update event, asking players waiting in a queue whether
+    // they are still there when they go offline
+    fn presence_update(&self, context: Context, update: PresenceUpdateEvent) {
+        if update.presence.status == OnlineStatus::Offline {
+            lobby::went_offline(&context.http, &context.data, *update.presence.user_id.as_u64());
+        }
+    }
     // handle resume event
     fn resume(&self, context: Context, _: ResumedEvent) {
         let log = context.data.read().get::<Log>().cloned().unwrap();
@@ -112,22 +252,83 @@ impl EventHandler for Handler {
 
 // General structure for bot framework
 #[group]
-#[commands(ping, subscribe, unsubscribe)]
+#[commands(abandon, leaderboard, link, pick, ping, ready, report, stats, status, subscribe, unsubscribe, vote, who)]
 struct General;
 
+// Admin structure for bot framework, limited to members with an admin role
+#[group]
+#[checks(Admin)]
+#[commands(adjustrating, ban, cancelmatch, disputes, endseason, forceadd, forceremove, setresult, startseason, unban)]
+struct Admin;
+
 // ShardManagerContainer for bot framework
 struct ShardManagerContainer;
 
+// TypeMapKey implementation for Admins
+impl TypeMapKey for Admins {
+    type Value = Arc<Admins>;
+}
+
+// TypeMapKey implementation for Channels
+impl TypeMapKey for Channels {
+    type Value = Arc<Channels>;
+}
+
 // TypeMapKey implementation for Database
 impl TypeMapKey for Database {
     type Value = Arc<Database>;
 }
 
+// TypeMapKey implementation for Expiries
+impl TypeMapKey for Expiries {
+    type Value = Arc<Mutex<Expiries>>;
+}
+
+// TypeMapKey implementation for Leaderboards
+impl TypeMapKey for Leaderboards {
+    type Value = Arc<Mutex<Leaderboards>>;
+}
+
+// TypeMapKey implementation for Links
+impl TypeMapKey for Links {
+    type Value = Arc<Mutex<Links>>;
+}
+
+// TypeMapKey implementation for MapVotes
+impl TypeMapKey for MapVotes {
+    type Value = Arc<Mutex<MapVotes>>;
+}
+
+// TypeMapKey implementation for Matches
+impl TypeMapKey for Matches {
+    type Value = Arc<Mutex<Matches>>;
+}
+
+// TypeMapKey implementation for Queues
+impl TypeMapKey for Queues {
+    type Value = Arc<Mutex<Queues>>;
+}
+
+// TypeMapKey implementation for ReadyChecks
+impl TypeMapKey for ReadyChecks {
+    type Value = Arc<Mutex<ReadyChecks>>;
+}
+
+// TypeMapKey implementation for Servers
+impl TypeMapKey for Servers {
+    type Value = Arc<Servers>;
+}
+
 // TypeMapKey implementation for ShardManagerContainer
 impl TypeMapKey for ShardManagerContainer {
     type Value = Arc<Mutex<ShardManager>>;
 }
 
+// TypeMapKey implementation for Drafts
+impl TypeMapKey for Drafts {
+    type Value = Arc<Mutex<Drafts>>;
+}
+
 // TypeMapKey implementation for Log
 impl TypeMapKey for Log {
     type Value = Arc<Log>;
@@ -0,0 +1,62 @@
+use crate::bot::lobby;
+use crate::logger::Log;
+use crate::match_making::servers::Servers;
+use et_mm_bot::et::log:: {
+    parse_line,
+    Clients,
+    LogTail
+};
+use serenity:: {
+    http::Http,
+    prelude::*
+};
+use std:: {
+    sync::Arc,
+    thread,
+    time::Duration
+};
+
+/// interval between checks of the timed phases of the match lifecycle.
+const TICK: Duration = Duration::from_secs(1);
+
+/// spawns the background thread that drives the timed phases of the match
+/// lifecycle, such as expiring ready checks, and follows the logs of every
+/// game server that has one configured.
+///
+/// # Example
+///
+/// ```
+/// timer::spawn(Arc::clone(&client.cache_and_http.http), Arc::clone(&client.data));
+/// ```
+pub fn spawn (http: Arc<Http>, data: Arc<RwLock<ShareMap>>) {
+    let servers = data.read().get::<Servers>().cloned().unwrap();
+    let mut tails: Vec<(String, LogTail, Clients)> = servers.all().iter()
+        .filter_map(|s| s.log.as_ref().map(|l| (s.name.clone(), LogTail::construct(l), Clients::construct())))
+        .collect();
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        for (server, tail, clients) in tails.iter_mut() {
+            read_log(&http, &data, server, tail, clients);
+        }
+        lobby::tick(&http, &data);
+    });
+}
+
+/// passes every event written to a game server log since the last tick on to
+/// the match lifecycle, keeping track of the clients connected to the server.
+/// each event is handled before it is applied to the clients, so the userinfo
+/// of a disconnecting client is still known.
+fn read_log (http: &Http, data: &RwLock<ShareMap>, server: &str, tail: &mut LogTail, clients: &mut Clients) {
+    let lines = match tail.read_lines() {
+        Ok (l) => l,
+        Err(e) => {
+            let log = data.read().get::<Log>().cloned().unwrap();
+            error!(log.logger, "\tfailed to read server log: {}", e; "server" => server);
+            return;
+        }
+    };
+    for event in lines.iter().filter_map(|l| parse_line(l)) {
+        lobby::server_event(http, data, server, clients, &event);
+        clients.update(&event);
+    }
+}
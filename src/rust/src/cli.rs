@@ -0,0 +1,103 @@
+use crate::logger::Format;
+use clap:: {
+    Parser,
+    Subcommand,
+    ValueEnum
+};
+use slog::Level;
+
+/// environment variables read by the command line interface itself rather
+/// than overriding configuration keys.
+pub const VARIABLES: [&str; 3] = ["ETMM_CONFIG", "ETMM_LOG_LEVEL", "ETMM_LOG_FORMAT"];
+
+/// Cli structure for the command line arguments of the bot
+///
+/// # Members
+///
+///     ```
+///     config: path of the toml configuration file
+///     log_level: least severe level of log records that are written
+///     log_format: how log records are written
+///     dry_run: check everything without changing the database or files
+///     command: what to do, running the bot if none is given
+///     ```
+#[derive(Debug, Parser)]
+#[command(version, about = "discord match making bot for Wolfenstein: Enemy Territory", long_about = None)]
+#[command(after_help = "every configuration key can be overridden by an environment variable named \
+    after its section and key, such as ETMM_DISCORD_TOKEN, ETMM_DATABASE_HOST or \
+    ETMM_MATCH_MAKING_READY_TIMEOUT. keys of a listed group or server also take its name, \
+    such as ETMM_SERVERS_EU1_RCON_PASSWORD.")]
+pub struct Cli {
+    #[arg(long, short, env = "ETMM_CONFIG", default_value = "/opt/et-mm-bot/config.toml",
+        help = "path of the toml configuration file. a config.cfg next to it is converted once")]
+    pub config: String,
+    #[arg(long, env = "ETMM_LOG_LEVEL", value_enum, default_value_t = LogLevel::Info,
+        help = "least severe level of log records that are written")]
+    pub log_level: LogLevel,
+    #[arg(long, env = "ETMM_LOG_FORMAT", value_enum, default_value_t = Format::Full,
+        help = "how log records are written")]
+    pub log_format: Format,
+    #[arg(long, global = true, help = "check everything without changing the database or files")]
+    pub dry_run: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>
+}
+
+/// Command enumeration for the subcommands of the bot
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// runs the bot. with --dry-run it only checks the pending migrations
+    Run,
+    /// loads and verifies the configuration, then prints a summary of it
+    CheckConfig,
+    /// applies pending database schema migrations, then adds the configured
+    /// groups and servers to the database
+    Migrate,
+    /// adds a match making group to the configuration file and the database
+    AddGroup {
+        /// match making group name
+        name: String,
+        /// players needed for the queue to pop, if not derived from the name
+        #[arg(long)]
+        capacity: Option<usize>,
+        /// how teams are formed: `balance`, `draft` or `draft random`
+        #[arg(long, default_value = "balance")]
+        mode: String,
+        /// maps the group votes on
+        #[arg(long, num_args = 1..)]
+        maps: Vec<String>,
+        /// minutes a player may wait in the queue before being asked whether
+        /// they are still there
+        #[arg(long)]
+        subscription_ttl: Option<u64>,
+        /// discord channel ids hosting the group
+        #[arg(long = "channel")]
+        channels: Vec<u64>
+    }
+}
+
+/// LogLevel enumeration for the levels log records can be filtered by
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LogLevel {
+    Critical,
+    Error,
+    Warning,
+    Info,
+    Debug,
+    Trace
+}
+
+// LogLevel implementation
+impl LogLevel {
+    /// returns the slog level of the log level.
+    pub fn level (self) -> Level {
+        match self {
+            LogLevel::Critical => Level::Critical,
+            LogLevel::Error => Level::Error,
+            LogLevel::Warning => Level::Warning,
+            LogLevel::Info => Level::Info,
+            LogLevel::Debug => Level::Debug,
+            LogLevel::Trace => Level::Trace
+        }
+    }
+}
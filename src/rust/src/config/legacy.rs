@@ -0,0 +1,181 @@
+use crate::config:: {
+    Config,
+    DatabaseSettings,
+    DiscordSettings,
+    GroupSettings,
+    MatchMakingSettings
+};
+use crate::match_making:: {
+    draft::TeamSelection,
+    servers::Server
+};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::io:: {
+    prelude::*,
+    BufReader
+};
+
+/// reads a configuration file in the line based `key: value` format used
+/// before the toml configuration. this is only kept around to convert old
+/// configuration files, see config::convert.
+///
+/// # Example
+///
+/// ```
+/// let config = legacy::read("config.cfg").unwrap();
+/// ```
+pub fn read (path: &str) -> Result<Config, Box<dyn Error>> {
+    let config = File::open(path)?;
+    let reader = BufReader::new(config);
+    let mut database = DatabaseSettings::default();
+    let mut discord = DiscordSettings::default();
+    let mut match_making = MatchMakingSettings::default();
+    let mut groups: Vec<GroupSettings> = Vec::new();
+    let mut servers: Vec<Server> = Vec::new();
+    let mut admins: BTreeMap<String, u64> = BTreeMap::new();
+    // group settings may be listed before their group, so they are kept
+    // aside until every line is read
+    let mut map_pools: Vec<(String, Vec<String>)> = Vec::new();
+    let mut subscription_ttls: Vec<(String, u64)> = Vec::new();
+    let mut channel_groups: Vec<(u64, Vec<String>)> = Vec::new();
+
+    // parse the configuration file
+    let mut section_name: String = String::from("");
+    for line in reader.lines() {
+        let line = line?;
+        if line.starts_with('[') && line.ends_with(']') {
+           section_name = String::from(&line.to_owned());
+        }
+        if line != section_name && !section_name.is_empty() {
+            let tokens: Vec<&str> = line.split(':').collect();
+            match section_name.as_ref() {
+                // parse database settings
+                "[database]" => {
+                    if let 2 = tokens.len() {
+                        match tokens[0] {
+                            "host" => database.host = tokens[1].trim().to_string(),
+                            "user" => database.user = tokens[1].trim().to_string(),
+                            _ => return Err(format!("unknown key in database section: {}", tokens[0]).into())
+                        };
+                    };
+                },
+                // parse discord configuration
+                "[discord]" => {
+                    if let 2 = tokens.len() {
+                        match tokens[0] {
+                            "token" => discord.token = tokens[1].trim().to_string(),
+                            _ => return Err(format!("unknown key in discord section: {}", tokens[0]).into())
+                        };
+                    };
+                },
+                // parse match making settings
+                "[match-making]" => {
+                    if let 2 = tokens.len() {
+                        match tokens[0] {
+                            "ready_timeout" => match_making.ready_timeout = tokens[1].trim().parse()?,
+                            "pick_timeout" => match_making.pick_timeout = tokens[1].trim().parse()?,
+                            "vote_timeout" => match_making.vote_timeout = tokens[1].trim().parse()?,
+                            "recent_maps" => match_making.recent_maps = tokens[1].trim().parse()?,
+                            "provisional_games" => match_making.provisional_games = tokens[1].trim().parse()?,
+                            "subscription_ttl" => match_making.subscription_ttl = tokens[1].trim().parse()?,
+                            "expiry_grace" => match_making.expiry_grace = tokens[1].trim().parse()?,
+                            _ => return Err(format!("unknown key in match-making section: {}", tokens[0]).into())
+                        };
+                    };
+                },
+                // parse match making groups and their optional team selection
+                "[mm-groups]" => {
+                    let name = tokens[0].trim().to_lowercase();
+                    if !name.is_empty() {
+                        let mut group = GroupSettings::construct(&name);
+                        if let 2 = tokens.len() {
+                            group.mode = TeamSelection::parse(tokens[1])?;
+                        }
+                        groups.push(group);
+                    }
+                },
+                // parse map pools of match making groups
+                "[maps]" => {
+                    if let 2 = tokens.len() {
+                        let maps = tokens[1].split_whitespace()
+                            .map(|m| m.to_lowercase())
+                            .collect();
+                        map_pools.push((tokens[0].trim().to_lowercase(), maps));
+                    };
+                },
+                // parse subscription time to live of match making groups
+                "[subscription-ttl]" => {
+                    if let 2 = tokens.len() {
+                        subscription_ttls.push((tokens[0].trim().to_lowercase(), tokens[1].trim().parse()?));
+                    };
+                },
+                // parse game servers, splitting on the first colon only since
+                // rcon passwords may contain colons
+                "[servers]" => {
+                    let tokens: Vec<&str> = line.splitn(2, ':').collect();
+                    if let 2 = tokens.len() {
+                        servers.push(Server::parse(tokens[0], tokens[1])?);
+                    };
+                },
+                // parse admin roles, each labelled with a name for readability
+                "[admins]" => {
+                    if let 2 = tokens.len() {
+                        match tokens[1].trim().parse() {
+                            Ok (r) => admins.insert(tokens[0].trim().to_string(), r),
+                            Err(_) => return Err(format!("invalid role id in admins section: {}", tokens[1].trim()).into())
+                        };
+                    };
+                },
+                // parse the match making groups hosted in each channel
+                "[channels]" => {
+                    if let 2 = tokens.len() {
+                        let channel = match tokens[0].trim().parse() {
+                            Ok (c) => c,
+                            Err(_) => return Err(format!("invalid channel id in channels section: {}", tokens[0].trim()).into())
+                        };
+                        let names = tokens[1].split_whitespace()
+                            .map(|g| g.to_lowercase())
+                            .collect();
+                        channel_groups.push((channel, names));
+                    };
+                },
+                _ => return Err(format!("unknown section in file: {}", section_name).into())
+            };
+        }
+    }
+    // move the per group settings into their groups
+    for (name, maps) in map_pools.into_iter() {
+        find(&mut groups, &name, "map pool")?.maps = maps;
+    }
+    for (name, ttl) in subscription_ttls.into_iter() {
+        find(&mut groups, &name, "subscription ttl")?.subscription_ttl = Some(ttl);
+    }
+    for (channel, names) in channel_groups.into_iter() {
+        for name in names.iter() {
+            find(&mut groups, name, "channel")?.channels.push(channel);
+        }
+    }
+    // return
+    Ok (
+        Config {
+            database,
+            discord,
+            match_making,
+            groups,
+            servers,
+            admins,
+            overrides: Vec::new()
+        }
+    )
+}
+
+/// returns the settings of the match making group with the specified name,
+/// or an error naming the section that referred to an unknown group.
+fn find<'a> (groups: &'a mut [GroupSettings], name: &str, section: &str) -> Result<&'a mut GroupSettings, Box<dyn Error>> {
+    match groups.iter_mut().find(|g| g.name == name) {
+        Some(g) => Ok (g),
+        None => Err(format!("{} information: unknown match making group: {}", section, name).into())
+    }
+}
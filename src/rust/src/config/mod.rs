@@ -0,0 +1,783 @@
+mod legacy;
+
+use crate::cli;
+use crate::match_making:: {
+    draft::TeamSelection,
+    queue,
+    servers::Server
+};
+use serde:: {
+    Deserialize,
+    Serialize
+};
+use std:: {
+    collections:: {
+        BTreeMap,
+        HashMap
+    },
+    env,
+    error::Error,
+    fs,
+    fs::OpenOptions,
+    io::Write,
+    time::Duration
+};
+use toml:: {
+    value::Table,
+    Value
+};
+
+/// prefix of the environment variables that override configuration keys.
+const ENV_PREFIX: &str = "ETMM_";
+
+/// SslMode enumeration for whether the connection to the postgres server is
+/// encrypted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SslMode {
+    /// never use tls
+    #[default]
+    Disable,
+    /// use tls if the server supports it
+    Prefer,
+    /// fail unless the server supports tls
+    Require
+}
+
+// SslMode implementation
+impl SslMode {
+    /// returns the name of the ssl mode as written in the configuration file.
+    pub fn as_str (self) -> &'static str {
+        match self {
+            SslMode::Disable => "disable",
+            SslMode::Prefer => "prefer",
+            SslMode::Require => "require"
+        }
+    }
+}
+
+/// DatabaseSettings structure for the database section of the configuration
+/// file
+///
+/// # Members
+///
+///     ```
+///     host: hostname or ip address of the postgres server, or the
+///         directory of its unix socket
+///     port: port of the postgres server, if not the default
+///     user: postgres user the bot connects as
+///     password: password of the postgres user, if any
+///     password_file: file holding the password of the postgres user, if any
+///     dbname: database the bot connects to, if not named after the user
+///     sslmode: whether the connection is encrypted, `disable` if not set
+///     application_name: name the connection is shown with on the server
+///     connect_timeout: seconds to wait for the server to accept the
+///         connection, waiting indefinitely if not set or zero
+///     ca_file: pem file of the certificate authorities trusted to sign the
+///         certificate of the server, the webpki roots if not set
+///     pool_size: most connections kept open to the server, 8 if not set
+///     pool_timeout: seconds to wait for a free connection or for the server
+///         to accept a new one, 30 if not set
+///     ```
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    pub host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    pub user: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dbname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sslmode: Option<SslMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub application_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_size: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_timeout: Option<u64>
+}
+
+// DatabaseSettings implementation
+impl DatabaseSettings {
+    /// returns the settings for connecting to the postgres database, reading
+    /// the password from its file if one is configured. like libpq, tls is
+    /// never used for a unix socket whatever the sslmode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = config.database.postgres_config().unwrap().connect(NoTls).unwrap();"
+    /// ```
+    pub fn postgres_config (&self) -> Result<postgres::Config, Box<dyn Error>> {
+        let mut config = postgres::Config::new();
+        config.host(&self.host)
+            .user(&self.user)
+            .application_name(self.application_name.as_deref().unwrap_or("et-mm-bot"))
+            .ssl_mode(match self.sslmode.unwrap_or_default() {
+                _ if self.host.starts_with('/') => postgres::config::SslMode::Disable,
+                SslMode::Disable => postgres::config::SslMode::Disable,
+                SslMode::Prefer => postgres::config::SslMode::Prefer,
+                SslMode::Require => postgres::config::SslMode::Require
+            });
+        if let Some(p) = self.port {
+            config.port(p);
+        }
+        if let Some(d) = &self.dbname {
+            config.dbname(d);
+        }
+        if let Some(t) = self.connect_timeout.filter(|t| *t > 0) {
+            config.connect_timeout(Duration::from_secs(t));
+        }
+        match (&self.password, &self.password_file) {
+            (Some(p), _) => {
+                config.password(p);
+            },
+            (None, Some(f)) => match fs::read_to_string(f) {
+                Ok (p) => {
+                    config.password(p.trim_end_matches(['\n', '\r']));
+                },
+                Err(e) => return Err(format!("database information: cannot read password file {}: {}", f, e).into())
+            },
+            (None, None) => ()
+        };
+        Ok (config)
+    }
+    /// returns the most connections kept open to the postgres server.
+    pub fn pool_size (&self) -> u32 {
+        self.pool_size.unwrap_or(8)
+    }
+    /// returns how long to wait for a connection to the postgres server.
+    pub fn pool_timeout (&self) -> Duration {
+        Duration::from_secs(self.pool_timeout.unwrap_or(30))
+    }
+    /// returns where the bot connects to as `user@host:port/dbname`, leaving
+    /// out the password so it can be logged.
+    pub fn address (&self) -> String {
+        let mut address = format!("{}@{}", self.user, self.host);
+        if let Some(p) = self.port {
+            address.push_str(&format!(":{}", p));
+        }
+        if let Some(d) = &self.dbname {
+            address.push_str(&format!("/{}", d));
+        }
+        address
+    }
+}
+
+/// DiscordSettings structure for the discord section of the configuration
+/// file
+///
+/// # Members
+///
+///     ```
+///     token: token for discord bot api
+///     ```
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscordSettings {
+    pub token: String
+}
+
+/// MatchMakingSettings structure for the match-making section of the
+/// configuration file. every setting is optional.
+///
+/// # Members
+///
+///     ```
+///     ready_timeout: seconds players have to acknowledge a ready check
+///     pick_timeout: seconds a draft captain has for each pick
+///     vote_timeout: seconds a map vote runs for
+///     recent_maps: number of recently played maps left out of the next map vote
+///     provisional_games: completed matches a player needs to appear on leaderboards
+///     subscription_ttl: minutes a player may wait in a queue before being asked
+///         whether they are still there, or 0 to never ask
+///     expiry_grace: seconds a player has to answer before being unsubscribed
+///     ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MatchMakingSettings {
+    pub ready_timeout: u64,
+    pub pick_timeout: u64,
+    pub vote_timeout: u64,
+    pub recent_maps: usize,
+    pub provisional_games: usize,
+    pub subscription_ttl: u64,
+    pub expiry_grace: u64
+}
+
+// Default implementation for MatchMakingSettings
+impl Default for MatchMakingSettings {
+    fn default () -> Self {
+        Self {
+            ready_timeout: 60,
+            pick_timeout: 30,
+            vote_timeout: 45,
+            recent_maps: 2,
+            provisional_games: 5,
+            subscription_ttl: 120,
+            expiry_grace: 300
+        }
+    }
+}
+
+/// GroupSettings structure for a match making group of the configuration file
+///
+/// # Members
+///
+///     ```
+///     name: match making group name
+///     capacity: players needed for the queue to pop, if not derived from the
+///         `<n>v<n>` name
+///     mode: how the group forms its teams
+///     maps: maps the group votes on, if any
+///     subscription_ttl: subscription_ttl of the group, if it overrides the
+///         match-making section
+///     channels: discord channel ids hosting the group
+///     ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GroupSettings {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<usize>,
+    #[serde(default)]
+    pub mode: TeamSelection,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub maps: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subscription_ttl: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channels: Vec<u64>
+}
+
+// GroupSettings implementation
+impl GroupSettings {
+    /// constructs the settings of a match making group that only has a name.
+    pub fn construct (name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            capacity: None,
+            mode: TeamSelection::Balance,
+            maps: Vec::new(),
+            subscription_ttl: None,
+            channels: Vec::new()
+        }
+    }
+}
+
+/// Config structure for bot configuration
+///
+/// # Members
+///
+///     ```
+///     database: postgres database settings
+///     discord: discord bot settings
+///     match_making: timeouts and limits shared by every match making group
+///     groups: match making groups in the order they are listed
+///     servers: game servers matches are allocated to
+///     admins: discord role ids whose members may use admin commands, each
+///         labelled with a name for readability
+///     overrides: environment variables that overrode configuration keys
+///     ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub database: DatabaseSettings,
+    #[serde(default)]
+    pub discord: DiscordSettings,
+    #[serde(default, rename = "match-making")]
+    pub match_making: MatchMakingSettings,
+    #[serde(default)]
+    pub groups: Vec<GroupSettings>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub admins: BTreeMap<String, u64>,
+    #[serde(skip)]
+    pub overrides: Vec<String>
+}
+
+// Config implmentation
+impl Config {
+    /// reads the bot configuration from a toml configuration file into
+    /// memory, overriding its keys with the ETMM_ environment variables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let config = config::Config::construct("config.toml").unwrap();"
+    /// ```
+    pub fn construct (path: &str) -> Result<Self, Box<dyn Error>> {
+        Self::parse(&fs::read_to_string(path)?, env::vars())
+    }
+    /// parses and verifies the bot configuration from the text of a toml
+    /// configuration file and the specified environment variables. syntax
+    /// errors, unknown keys and values of the wrong type are reported with
+    /// their line and column.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let config = config::Config::parse("[database]\nhost = \"localhost\"\n...", env::vars()).unwrap();"
+    /// ```
+    pub fn parse<I: IntoIterator<Item = (String, String)>> (text: &str, environment: I) -> Result<Self, Box<dyn Error>> {
+        let config: Self = match toml::from_str(text) {
+            Ok (c) => c,
+            Err(e) => return Err(describe(text, &e).into())
+        };
+        let mut config = config.override_with(environment)?;
+        config.verify()?;
+        Ok (config)
+    }
+    /// overrides configuration keys with the environment variables named
+    /// after them, such as ETMM_DISCORD_TOKEN for the token key of the
+    /// discord section. keys of a listed group or server also take its name,
+    /// such as ETMM_SERVERS_EU1_RCON_PASSWORD. values are read as the type
+    /// of the key they override, with lists separated by commas or spaces.
+    fn override_with<I: IntoIterator<Item = (String, String)>> (self, environment: I) -> Result<Self, Box<dyn Error>> {
+        let mut value = Value::try_from(&self)?;
+        let template = Value::try_from(template())?;
+        let mut overrides = Vec::new();
+        for (variable, text) in environment.into_iter() {
+            let key = match variable.strip_prefix(ENV_PREFIX) {
+                Some(k) if !cli::VARIABLES.contains(&variable.as_str()) => k.to_lowercase(),
+                _ => continue
+            };
+            if let Err(e) = assign(&mut value, &template, &key, &text) {
+                return Err(format!("environment variable {}: {}", variable, e).into());
+            }
+            overrides.push(variable);
+        }
+        if overrides.is_empty() {
+            return Ok (self);
+        }
+        let mut config: Self = match value.try_into() {
+            Ok (c) => c,
+            Err(e) => return Err(format!("environment variables {}: {}", overrides.join(", "), e).into())
+        };
+        overrides.sort();
+        config.overrides = overrides;
+        Ok (config)
+    }
+    /// normalizes names to lower case and verifies the settings that the
+    /// types of the configuration can not express.
+    fn verify (&mut self) -> Result<(), Box<dyn Error>> {
+        for group in self.groups.iter_mut() {
+            group.name = group.name.trim().to_lowercase();
+            group.maps = group.maps.iter().map(|m| m.to_lowercase()).collect();
+        }
+        for server in self.servers.iter_mut() {
+            server.name = server.name.trim().to_lowercase();
+            server.region = server.region.to_lowercase();
+        }
+        if self.database.host.is_empty() {
+            return Err("database information: host not in configuration file or ETMM_DATABASE_HOST".into());
+        } else if self.database.user.is_empty() {
+            return Err("database information: user not in configuration file or ETMM_DATABASE_USER".into());
+        } else if self.database.password.is_some() && self.database.password_file.is_some() {
+            return Err("database information: both a password and a password file are set".into());
+        } else if self.database.pool_size == Some(0) || self.database.pool_timeout == Some(0) {
+            return Err("database information: pool_size and pool_timeout must be more than 0".into());
+        } else if self.discord.token.is_empty() {
+            return Err("discord information: token not in configuration file or ETMM_DISCORD_TOKEN".into());
+        } else if self.groups.is_empty() {
+            return Err("match making group information: no match making groups in configuration file".into());
+        }
+        for (i, group) in self.groups.iter().enumerate() {
+            if self.groups[..i].iter().any(|g| g.name == group.name) {
+                return Err(format!("match making group information: duplicate match making group: {}", group.name).into());
+            }
+            match group.capacity {
+                Some(c) if c < 2 => return Err(format!("match making group information: capacity of {} is less than 2", group.name).into()),
+                Some(_) => (),
+                None => {
+                    if let Err(e) = queue::capacity(&group.name) {
+                        return Err(format!("match making group information: {}. set a capacity for it", e).into());
+                    }
+                }
+            };
+        }
+        for (i, server) in self.servers.iter().enumerate() {
+            if self.servers[..i].iter().any(|s| s.name == server.name) {
+                return Err(format!("server information: duplicate server name: {}", server.name).into());
+            }
+        }
+        Ok (())
+    }
+    /// returns the name of every match making group.
+    pub fn group_names (&self) -> Vec<String> {
+        self.groups.iter()
+            .map(|g| g.name.clone())
+            .collect()
+    }
+    /// returns the capacity of every match making group that sets one.
+    pub fn capacities (&self) -> HashMap<String, usize> {
+        self.groups.iter()
+            .filter_map(|g| g.capacity.map(|c| (g.name.clone(), c)))
+            .collect()
+    }
+    /// returns how each match making group forms its teams.
+    pub fn selections (&self) -> HashMap<String, TeamSelection> {
+        self.groups.iter()
+            .map(|g| (g.name.clone(), g.mode))
+            .collect()
+    }
+    /// returns the map pool of every match making group that has one.
+    pub fn map_pools (&self) -> HashMap<String, Vec<String>> {
+        self.groups.iter()
+            .filter(|g| !g.maps.is_empty())
+            .map(|g| (g.name.clone(), g.maps.clone()))
+            .collect()
+    }
+    /// returns the subscription_ttl of every match making group that
+    /// overrides the match-making section.
+    pub fn subscription_ttls (&self) -> HashMap<String, u64> {
+        self.groups.iter()
+            .filter_map(|g| g.subscription_ttl.map(|t| (g.name.clone(), t)))
+            .collect()
+    }
+    /// returns the match making groups hosted in each configured channel.
+    pub fn channel_groups (&self) -> HashMap<u64, Vec<String>> {
+        let mut channels: HashMap<u64, Vec<String>> = HashMap::new();
+        for group in self.groups.iter() {
+            for channel in group.channels.iter() {
+                channels.entry(*channel).or_default().push(group.name.clone());
+            }
+        }
+        channels
+    }
+    /// returns the discord role ids of the admin roles.
+    pub fn admin_roles (&self) -> Vec<u64> {
+        self.admins.values().cloned().collect()
+    }
+}
+
+/// returns a configuration with every optional key set and one entry in
+/// every list, which tells the type of the keys environment variables can
+/// override. the admin role named `*` stands for any name.
+fn template () -> Config {
+    let mut group = GroupSettings::construct("");
+    group.capacity = Some(0);
+    group.maps = vec![String::new()];
+    group.subscription_ttl = Some(0);
+    group.channels = vec![0];
+    let mut admins = BTreeMap::new();
+    admins.insert("*".to_string(), 0);
+    Config {
+        database: DatabaseSettings {
+            host: String::new(),
+            port: Some(0),
+            user: String::new(),
+            password: Some(String::new()),
+            password_file: Some(String::new()),
+            dbname: Some(String::new()),
+            sslmode: Some(SslMode::Disable),
+            application_name: Some(String::new()),
+            connect_timeout: Some(0),
+            ca_file: Some(String::new()),
+            pool_size: Some(0),
+            pool_timeout: Some(0)
+        },
+        discord: DiscordSettings::default(),
+        match_making: MatchMakingSettings::default(),
+        groups: vec![group],
+        servers: vec![
+            Server {
+                name: String::new(),
+                host: String::new(),
+                port: 0,
+                rcon_password: String::new(),
+                region: String::new(),
+                log: Some(String::new())
+            }
+        ],
+        admins,
+        overrides: Vec::new()
+    }
+}
+
+/// returns the name of a group or server as it appears in environment
+/// variable names, lower cased.
+fn variable_name (name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// overrides the configuration key named by the lower cased environment
+/// variable key, without its prefix, with the specified text.
+fn assign (value: &mut Value, template: &Value, key: &str, text: &str) -> Result<(), Box<dyn Error>> {
+    let sections = template.as_table().unwrap();
+    let config = value.as_table_mut().unwrap();
+    for (section, kinds) in sections.iter() {
+        let rest = match key.strip_prefix(&format!("{}_", variable_name(section))) {
+            Some(r) => r,
+            None => continue
+        };
+        let entry = config.entry(section.clone()).or_insert_with(|| match kinds {
+            Value::Array(_) => Value::Array(Vec::new()),
+            _ => Value::Table(Table::new())
+        });
+        return match (kinds, entry) {
+            (Value::Array(kinds), Value::Array(entries)) => {
+                for entry in entries.iter_mut() {
+                    let name = variable_name(entry.get("name").and_then(|n| n.as_str()).unwrap_or_default());
+                    if let Some(field) = rest.strip_prefix(&format!("{}_", name)) {
+                        return set(entry.as_table_mut().unwrap(), &kinds[0], field, text);
+                    }
+                }
+                Err(format!("no entry of the {} section is named in it", section).into())
+            },
+            (kinds, Value::Table(table)) => set(table, kinds, rest, text),
+            _ => Err(format!("unexpected type of the {} section", section).into())
+        };
+    }
+    Err("unknown configuration key".into())
+}
+
+/// sets a key of a table to the specified text, read as the type the key has
+/// in the template.
+fn set (table: &mut Table, kinds: &Value, field: &str, text: &str) -> Result<(), Box<dyn Error>> {
+    let kind = match kinds.get(field).or_else(|| kinds.get("*")) {
+        Some(k) => k,
+        None => return Err(format!("unknown configuration key: {}", field).into())
+    };
+    table.insert(field.to_string(), read(kind, text)?);
+    Ok (())
+}
+
+/// reads the text of an environment variable as the type of the specified
+/// template value.
+fn read (kind: &Value, text: &str) -> Result<Value, Box<dyn Error>> {
+    match kind {
+        Value::String(_) => Ok (Value::String(text.to_string())),
+        Value::Integer(_) => match text.trim().parse() {
+            Ok (i) => Ok (Value::Integer(i)),
+            Err(_) => Err(format!("expected an integer: {}", text).into())
+        },
+        Value::Boolean(_) => match text.trim().parse() {
+            Ok (b) => Ok (Value::Boolean(b)),
+            Err(_) => Err(format!("expected `true` or `false`: {}", text).into())
+        },
+        Value::Array(kinds) => {
+            let mut values = Vec::new();
+            for item in text.split(|c: char| c == ',' || c.is_whitespace()).filter(|i| !i.is_empty()) {
+                values.push(read(&kinds[0], item)?);
+            }
+            Ok (Value::Array(values))
+        },
+        _ => Err("unsupported configuration value".into())
+    }
+}
+
+/// formats a toml error with its line and column counting from one. unknown
+/// keys are reported at the start of their table by the toml parser, so the
+/// key itself is looked up to point at it instead.
+fn describe (text: &str, error: &toml::de::Error) -> String {
+    let message = error.to_string();
+    let message = match message.rfind(" at line ") {
+        Some(i) => message[..i].to_string(),
+        None => message
+    };
+    let (mut line, mut column) = match error.line_col() {
+        Some(p) => p,
+        None => return message
+    };
+    let unknown = message.strip_prefix("unknown field `")
+        .and_then(|m| m.split('`').next());
+    if let Some(key) = unknown {
+        for (i, text) in text.lines().enumerate().skip(line) {
+            let trimmed = text.trim_start();
+            if trimmed.starts_with('[') && i > line {
+                break;
+            }
+            if trimmed.starts_with(key) && trimmed[key.len()..].trim_start().starts_with('=') {
+                line = i;
+                column = text.len() - trimmed.len();
+                break;
+            }
+        }
+    }
+    format!("line {}, column {}: {}", line + 1, column + 1, message)
+}
+
+/// converts a configuration file in the line based format used before the
+/// toml configuration into a toml configuration file. the old file is left
+/// in place.
+///
+/// # Example
+///
+/// ```
+/// config::convert("config.cfg", "config.toml").unwrap();"
+/// ```
+pub fn convert (legacy_path: &str, path: &str) -> Result<(), Box<dyn Error>> {
+    let mut config = legacy::read(legacy_path)?;
+    config.verify()?;
+    fs::write(path, toml::to_string(&config)?)?;
+    Ok (())
+}
+
+/// appends a match making group to the end of a toml configuration file,
+/// leaving the rest of the file and its comments untouched. the file is only
+/// written if it is still valid with the group added, and not at all for a
+/// dry run. returns the appended table.
+///
+/// # Example
+///
+/// ```
+/// config::append_group("config.toml", &GroupSettings::construct("2v2"), false).unwrap();"
+/// ```
+pub fn append_group (path: &str, group: &GroupSettings, dry_run: bool) -> Result<String, Box<dyn Error>> {
+    #[derive(Serialize)]
+    struct Groups<'a> {
+        groups: [&'a GroupSettings; 1]
+    }
+    let text = fs::read_to_string(path)?;
+    let config: Config = match toml::from_str(&text) {
+        Ok (c) => c,
+        Err(e) => return Err(describe(&text, &e).into())
+    };
+    if config.groups.iter().any(|g| g.name == group.name.to_lowercase()) {
+        return Err(format!("match making group already exists: {}", group.name).into());
+    }
+    let table = toml::to_string(&Groups { groups: [group] })?;
+    let separator = if text.is_empty() || text.ends_with("\n\n") { "" } else if text.ends_with('\n') { "\n" } else { "\n\n" };
+    Config::parse(&format!("{}{}{}", text, separator, table), env::vars())?;
+    if !dry_run {
+        let mut file = OpenOptions::new().append(true).open(path)?;
+        write!(file, "{}{}", separator, table)?;
+    }
+    Ok (table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::match_making::draft::CaptainSelection;
+    use std:: {
+        env,
+        path::PathBuf,
+        process
+    };
+
+    const EXAMPLE: &str = include_str!("../../../../runtime/config.toml");
+
+    /// returns a path in the temporary directory that no other test run uses.
+    fn temp_path (name: &str) -> PathBuf {
+        env::temp_dir().join(format!("et-mm-bot-{}-{}", process::id(), name))
+    }
+
+    #[test]
+    fn example_configuration_is_valid() {
+        let config = Config::parse(EXAMPLE, Vec::new()).unwrap();
+        assert_eq!(config.group_names(), vec!["1v1", "3v3", "6v6"]);
+        assert_eq!(config.selections()["6v6"], TeamSelection::Draft(CaptainSelection::Highest));
+        assert_eq!(config.subscription_ttls()["1v1"], 30);
+        assert_eq!(config.channel_groups()[&234567890123456789], vec!["3v3", "6v6"]);
+        assert_eq!(config.servers[0].address(), "192.0.2.10:27960");
+    }
+
+    #[test]
+    fn unknown_keys_are_reported_with_their_position() {
+        let text = "[database]\nhost = \"localhost\"\n  hots = \"localhost\"\nuser = \"et_mm\"\n";
+        let error = Config::parse(text, Vec::new()).unwrap_err().to_string();
+        assert!(error.starts_with("line 3, column 3: unknown field `hots`"), "{}", error);
+        let text = "[database]\nhost = 5\n";
+        let error = Config::parse(text, Vec::new()).unwrap_err().to_string();
+        assert!(error.starts_with("line 2, column 8: invalid type"), "{}", error);
+    }
+
+    #[test]
+    fn environment_variables_override_keys() {
+        let text = EXAMPLE.replace("token = \"<discord_bot_token>\"\n", "");
+        assert!(Config::parse(&text, Vec::new()).is_err());
+        let environment = vec![
+            ("ETMM_DISCORD_TOKEN", "123"),
+            ("ETMM_DATABASE_PORT", "5433"),
+            ("ETMM_DATABASE_PASSWORD", "hunter2"),
+            ("ETMM_DATABASE_SSLMODE", "require"),
+            ("ETMM_MATCH_MAKING_READY_TIMEOUT", "90"),
+            ("ETMM_GROUPS_3V3_MAPS", "supply, radar"),
+            ("ETMM_SERVERS_EU2_RCON_PASSWORD", "secret"),
+            ("ETMM_ADMINS_REFEREES", "42"),
+            ("ETMM_CONFIG", "ignored.toml"),
+            ("PATH", "/usr/bin")
+        ];
+        let environment = environment.into_iter().map(|(k, v)| (k.to_string(), v.to_string()));
+        let config = Config::parse(&text, environment).unwrap();
+        assert_eq!(config.discord.token, "123");
+        assert_eq!(config.database.address(), "et_mm@localhost:5433");
+        let database = config.database.postgres_config().unwrap();
+        assert_eq!(database.get_password(), Some(&b"hunter2"[..]));
+        assert_eq!(database.get_ssl_mode(), postgres::config::SslMode::Require);
+        assert_eq!(config.match_making.ready_timeout, 90);
+        assert_eq!(config.map_pools()["3v3"], vec!["supply", "radar"]);
+        assert_eq!(config.servers[1].rcon_password, "secret");
+        assert_eq!(config.admins["referees"], 42);
+        assert_eq!(config.overrides.len(), 8);
+        for (key, value) in [("ETMM_DISCORD_TOKNE", "t"), ("ETMM_SERVERS_US1_PORT", "1"), ("ETMM_DATABASE_PORT", "x"), ("ETMM_DATABASE_SSLMODE", "always")].iter() {
+            assert!(Config::parse(EXAMPLE, vec![(key.to_string(), value.to_string())]).is_err());
+        }
+    }
+
+    #[test]
+    fn database_passwords_are_read_from_files() {
+        let file = temp_path("password");
+        fs::write(&file, "s3cret\n").unwrap();
+        let mut settings = Config::parse(EXAMPLE, Vec::new()).unwrap().database;
+        settings.password_file = Some(file.to_str().unwrap().to_string());
+        settings.dbname = Some("mm".to_string());
+        settings.connect_timeout = Some(5);
+        let database = settings.postgres_config().unwrap();
+        assert_eq!(database.get_password(), Some(&b"s3cret"[..]));
+        assert_eq!(database.get_dbname(), Some("mm"));
+        assert_eq!(database.get_connect_timeout(), Some(&Duration::from_secs(5)));
+        assert_eq!(database.get_ssl_mode(), postgres::config::SslMode::Disable);
+        assert_eq!(settings.address(), "et_mm@localhost/mm");
+        settings.host = "/run/postgresql".to_string();
+        settings.sslmode = Some(SslMode::Require);
+        assert_eq!(settings.postgres_config().unwrap().get_ssl_mode(), postgres::config::SslMode::Disable);
+        let text = EXAMPLE.replace("user = \"et_mm\"\n", "user = \"et_mm\"\npassword = \"a\"\npassword_file = \"b\"\n");
+        assert!(Config::parse(&text, Vec::new()).is_err());
+        fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn groups_need_a_capacity() {
+        let text = "[database]\nhost = \"localhost:5432\"\nuser = \"et_mm\"\n[discord]\ntoken = \"t\"\n[[groups]]\nname = \"ffa\"\n";
+        assert!(Config::parse(text, Vec::new()).is_err());
+        let config = Config::parse(&format!("{}capacity = 8\n", text), Vec::new()).unwrap();
+        assert_eq!(config.capacities()["ffa"], 8);
+    }
+
+    #[test]
+    fn legacy_configuration_converts() {
+        let legacy = temp_path("legacy.cfg");
+        let path = temp_path("converted.toml");
+        fs::write(&legacy, "[database]\nhost: localhost\nuser: et_mm\n\n[discord]\ntoken: t\n\n\
+            [mm-groups]\n3v3\n6v6: draft random\n\n[maps]\n6v6: Supply radar\n\n[subscription-ttl]\n3v3: 30\n\n\
+            [servers]\neu1: 192.0.2.10 27960 pass:word eu\n\n[channels]\n42: 3v3 6v6\n\n[admins]\nmods: 7\n").unwrap();
+        convert(legacy.to_str().unwrap(), path.to_str().unwrap()).unwrap();
+        let config = Config::parse(&fs::read_to_string(&path).unwrap(), Vec::new()).unwrap();
+        assert_eq!(config.database.address(), "et_mm@localhost");
+        assert_eq!(config.selections()["6v6"], TeamSelection::Draft(CaptainSelection::Random));
+        assert_eq!(config.map_pools()["6v6"], vec!["supply", "radar"]);
+        assert_eq!(config.subscription_ttls()["3v3"], 30);
+        assert_eq!(config.servers[0].rcon_password, "pass:word");
+        assert_eq!(config.channel_groups()[&42], vec!["3v3", "6v6"]);
+        assert_eq!(config.admin_roles(), vec![7]);
+        let _ = fs::remove_file(legacy);
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,238 @@
+use postgres:: {
+    Client,
+    Transaction
+};
+use std:: {
+    collections::HashMap,
+    error::Error
+};
+
+/// embeds a script of the database directory in the binary.
+macro_rules! script {
+    ($path:literal) => {
+        Script {
+            path: $path,
+            sql: include_str!(concat!("../../../database/", $path))
+        }
+    };
+}
+
+/// key of the advisory lock held while migrating, so two bots started at
+/// once do not apply the same migration twice.
+const LOCK: i64 = 0x45544d4d;
+
+/// table recording the applied migrations.
+const SCHEMA_MIGRATIONS: Script = script!("schema_migrations.pgsql");
+
+/// Script structure for a sql script embedded in the binary
+///
+/// # Members
+///
+///     ```
+///     path: path of the script within the database directory
+///     sql: contents of the script
+///     ```
+pub struct Script {
+    pub path: &'static str,
+    pub sql: &'static str
+}
+
+/// Migration structure for a versioned change of the database schema
+///
+/// # Members
+///
+///     ```
+///     version: version of the schema after the migration
+///     description: what the migration changes
+///     scripts: scripts run in order to apply the migration
+///     ```
+pub struct Migration {
+    pub version: i32,
+    pub description: &'static str,
+    pub scripts: &'static [Script]
+}
+
+// Migration implementation
+impl Migration {
+    /// returns the scripts of the migration as one text, which its checksum
+    /// is taken of.
+    fn sql (&self) -> String {
+        self.scripts.iter()
+            .map(|s| s.sql)
+            .collect::<Vec<&str>>()
+            .concat()
+    }
+}
+
+/// versioned migrations of the database schema in the order they are
+/// applied, each kept in the migrations directory under a name starting with
+/// its version. an applied migration must never change, since its checksum
+/// is checked against the database: schema changes go in a new migration
+/// instead. the first migration creates the tables if they do not exist, so
+/// it also applies to databases set up before migrations.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "initial schema",
+        scripts: &[
+            script!("migrations/1_initial_schema.pgsql")
+        ]
+    }
+];
+
+/// stored functions, replaced every time the migrations are run so they
+/// always match the binary. a function whose return type changes has to be
+/// dropped by a migration first.
+pub const FUNCTIONS: &[Script] = &[
+    script!("functions/internal/add_user.pgsql"),
+    script!("functions/external/add_match.pgsql"),
+    script!("functions/external/add_match_making_group.pgsql"),
+    script!("functions/external/add_match_making_user.pgsql"),
+    script!("functions/external/add_penalty.pgsql"),
+    script!("functions/external/add_player_stats.pgsql"),
+    script!("functions/external/add_server.pgsql"),
+    script!("functions/external/adjust_rating.pgsql"),
+    script!("functions/external/allocate_server.pgsql"),
+    script!("functions/external/cancel_match.pgsql"),
+    script!("functions/external/clear_match_making_user.pgsql"),
+    script!("functions/external/complete_match.pgsql"),
+    script!("functions/external/dispute_match.pgsql"),
+    script!("functions/external/end_season.pgsql"),
+    script!("functions/external/get_active_matches.pgsql"),
+    script!("functions/external/get_busy_servers.pgsql"),
+    script!("functions/external/get_disputed_matches.pgsql"),
+    script!("functions/external/get_drafts.pgsql"),
+    script!("functions/external/get_leaderboard.pgsql"),
+    script!("functions/external/get_linked_user.pgsql"),
+    script!("functions/external/get_map_votes.pgsql"),
+    script!("functions/external/get_match_making_ratings.pgsql"),
+    script!("functions/external/get_match_making_subscriptions.pgsql"),
+    script!("functions/external/get_match_making_users.pgsql"),
+    script!("functions/external/get_match_players.pgsql"),
+    script!("functions/external/get_penalties.pgsql"),
+    script!("functions/external/get_player_stats.pgsql"),
+    script!("functions/external/get_ready_checks.pgsql"),
+    script!("functions/external/get_recent_maps.pgsql"),
+    script!("functions/external/get_recent_player_matches.pgsql"),
+    script!("functions/external/get_season_standings.pgsql"),
+    script!("functions/external/lift_penalties.pgsql"),
+    script!("functions/external/link_user_identity.pgsql"),
+    script!("functions/external/release_server.pgsql"),
+    script!("functions/external/remove_draft.pgsql"),
+    script!("functions/external/remove_map_vote.pgsql"),
+    script!("functions/external/remove_match_making_user.pgsql"),
+    script!("functions/external/remove_ready_check.pgsql"),
+    script!("functions/external/requeue_match_making_user.pgsql"),
+    script!("functions/external/save_draft.pgsql"),
+    script!("functions/external/save_map_vote.pgsql"),
+    script!("functions/external/save_match_state.pgsql"),
+    script!("functions/external/save_ready_check.pgsql"),
+    script!("functions/external/start_season.pgsql")
+];
+
+/// runs a script, naming it in the error if it fails.
+fn execute (transaction: &mut Transaction, script: &Script) -> Result<(), Box<dyn Error>> {
+    match transaction.batch_execute(script.sql) {
+        Ok (()) => Ok (()),
+        Err(e) => Err(format!("{}: {}", script.path, super::pool::describe(&e)).into())
+    }
+}
+
+/// applies the pending migrations and replaces the stored functions in one
+/// transaction, which a dry run rolls back. fails without changing anything
+/// if the database was migrated by a newer binary or an applied migration
+/// changed since. returns the migrations that were pending.
+///
+/// # Example
+///
+/// ```
+/// let applied = migrations::apply(&mut client, false).unwrap();"
+/// ```
+pub fn apply (client: &mut Client, dry_run: bool) -> Result<Vec<&'static Migration>, Box<dyn Error>> {
+    let mut transaction = client.transaction()?;
+    transaction.query("SELECT pg_advisory_xact_lock ( $1 );", &[&LOCK])?;
+    execute(&mut transaction, &SCHEMA_MIGRATIONS)?;
+
+    // check the applied migrations against the embedded ones
+    let mut applied: HashMap<i32, String> = HashMap::new();
+    for row in transaction.query("SELECT version, checksum FROM schema_migrations;", &[])? {
+        applied.insert(row.get(0), row.get(1));
+    }
+    for (version, checksum) in applied.iter() {
+        let migration = match MIGRATIONS.iter().find(|m| m.version == *version) {
+            Some(m) => m,
+            None => return Err(format!("database schema version {} is newer than this bot, upgrade the bot", version).into())
+        };
+        let rows = transaction.query("SELECT md5 ( $1 );", &[&migration.sql()])?;
+        let expected: String = rows[0].get(0);
+        if *checksum != expected {
+            return Err(format!("migration {} ({}) changed after it was applied to the database", version, migration.description).into());
+        }
+    }
+
+    // apply the pending migrations in order
+    let mut pending = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| !applied.contains_key(&m.version)) {
+        for script in migration.scripts.iter() {
+            execute(&mut transaction, script)?;
+        }
+        transaction.execute(
+            "INSERT INTO schema_migrations (version, description, checksum) VALUES ( $1, $2, md5 ( $3 ) );",
+            &[&migration.version, &migration.description, &migration.sql()]
+        )?;
+        pending.push(migration);
+    }
+
+    // replace the stored functions
+    for function in FUNCTIONS.iter() {
+        execute(&mut transaction, function)?;
+    }
+    if dry_run {
+        transaction.rollback()?;
+    } else {
+        transaction.commit()?;
+    }
+    Ok (pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std:: {
+        fs,
+        path::Path
+    };
+
+    #[test]
+    fn versions_count_up_from_one() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, i as i32 + 1);
+            assert!(!migration.scripts.is_empty());
+            for script in migration.scripts.iter() {
+                assert!(script.path.starts_with(&format!("migrations/{}_", migration.version)), "{}", script.path);
+            }
+        }
+    }
+
+    #[test]
+    fn every_script_is_embedded() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../database");
+        let mut files = Vec::new();
+        for directory in ["migrations", "functions/internal", "functions/external"].iter() {
+            for entry in fs::read_dir(root.join(directory)).unwrap() {
+                let name = entry.unwrap().file_name().into_string().unwrap();
+                if name.ends_with(".pgsql") {
+                    files.push(format!("{}/{}", directory, name));
+                }
+            }
+        }
+        let mut embedded: Vec<String> = MIGRATIONS.iter()
+            .flat_map(|m| m.scripts.iter())
+            .chain(FUNCTIONS.iter())
+            .map(|s| s.path.to_string())
+            .collect();
+        files.sort();
+        embedded.sort();
+        assert_eq!(embedded, files);
+    }
+}
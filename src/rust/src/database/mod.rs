@@ -0,0 +1,1462 @@
+pub mod migrations;
+mod pool;
+
+use crate::config::DatabaseSettings;
+use crate::logger::Log;
+use crate::match_making:: {
+    balance:: {
+        RatedPlayer,
+        Teams
+    },
+    draft::Draft,
+    links::Identity,
+    map_vote::MapVote,
+    matches:: {
+        Match,
+        MatchResult,
+        Team
+    },
+    penalties:: {
+        Offence,
+        Penalty
+    },
+    rating,
+    ready_check::ReadyCheck,
+    stats::PlayerStats
+};
+use postgres:: {
+    Row,
+    config::SslMode,
+    types::Type
+};
+use r2d2:: {
+    Pool,
+    PooledConnection
+};
+use self::pool:: {
+    ConnectionManager,
+    ErrorLogger
+};
+use std:: {
+    collections::HashMap,
+    error::Error,
+    sync::Arc,
+    time:: {
+        Duration,
+        SystemTime
+    }
+};
+
+/// DisputedMatch structure for a match awaiting an admin decision
+///
+/// # Members
+///
+///     ```
+///     match_id: database match id
+///     group: match making group the match was formed from
+///     created_at: time the match was formed
+///     ```
+pub struct DisputedMatch {
+    pub match_id: i32,
+    pub group: String,
+    pub created_at: SystemTime
+}
+
+/// LifetimeStats structure for the statistics of a user over every completed match
+///
+/// # Members
+///
+///     ```
+///     matches: completed matches played
+///     wins: matches won
+///     losses: matches lost
+///     draws: matches drawn
+///     kills, deaths, gibs, revives, damage_given, damage_received, objectives:
+///         statistics summed over every match with recorded statistics
+///     hits: shots that hit an enemy, over every weapon
+///     shots: shots fired, over every weapon
+///     headshots: hits to the head, over every weapon
+///     ```
+pub struct LifetimeStats {
+    pub matches: i64,
+    pub wins: i64,
+    pub losses: i64,
+    pub draws: i64,
+    pub kills: i64,
+    pub deaths: i64,
+    pub gibs: i64,
+    pub revives: i64,
+    pub damage_given: i64,
+    pub damage_received: i64,
+    pub objectives: i64,
+    pub hits: i64,
+    pub shots: i64,
+    pub headshots: i64
+}
+
+/// RecentMatch structure for a recently completed match of a user
+///
+/// # Members
+///
+///     ```
+///     match_id: database match id
+///     outcome: outcome of the match for the user (win, loss, draw)
+///     kills: kills of the user, if statistics were recorded
+///     deaths: deaths of the user, if statistics were recorded
+///     ```
+pub struct RecentMatch {
+    pub match_id: i32,
+    pub outcome: String,
+    pub kills: Option<i32>,
+    pub deaths: Option<i32>
+}
+
+/// Standing structure for a ranked player on a leaderboard
+///
+/// # Members
+///
+///     ```
+///     rank: position of the player on the leaderboard
+///     discord_uuid: discord unique user id snowflake of the player
+///     rating: rating of the player within the group
+///     games: completed matches played
+///     wins: matches won
+///     losses: matches lost
+///     draws: matches drawn
+///     streak: current streak, such as W3 or L1
+///     ```
+pub struct Standing {
+    pub rank: i64,
+    pub discord_uuid: u64,
+    pub rating: f64,
+    pub games: i64,
+    pub wins: i64,
+    pub losses: i64,
+    pub draws: i64,
+    pub streak: String
+}
+
+/// Subscription structure for a player waiting in a match making queue
+///
+/// # Members
+///
+///     ```
+///     discord_uuid: discord unique user id snowflake of the player
+///     subscribed_at: time the player subscribed to the group
+///     ```
+pub struct Subscription {
+    pub discord_uuid: u64,
+    pub subscribed_at: SystemTime
+}
+
+/// returns the standings and the number of ranked players for the rows of a
+/// leaderboard query, whose columns are those of a standing followed by the
+/// number of ranked players.
+fn standings (rows: &[Row]) -> Result <(Vec<Standing>, i64), Box<dyn Error>> {
+    let mut standings = Vec::new();
+    let mut ranked = 0;
+    for row in rows.iter() {
+        let discord_uuid: String = row.get(1);
+        standings.push(Standing {
+            rank: row.get(0),
+            discord_uuid: discord_uuid.parse()?,
+            rating: row.get(2),
+            games: row.get(3),
+            wins: row.get(4),
+            losses: row.get(5),
+            draws: row.get(6),
+            streak: row.get(7)
+        });
+        ranked = row.get(8);
+    }
+    Ok ((standings, ranked))
+}
+
+/// returns the database representation of a list of discord uuids.
+fn uuid_strings (discord_uuids: &[u64]) -> Vec<String> {
+    discord_uuids.iter()
+        .map(|u| u.to_string())
+        .collect()
+}
+
+/// parses a list of discord uuids read from the database.
+fn parse_uuids (discord_uuids: &[String]) -> Result <Vec<u64>, Box<dyn Error>> {
+    let mut parsed = Vec::new();
+    for discord_uuid in discord_uuids.iter() {
+        parsed.push(discord_uuid.parse::<u64>()?);
+    }
+    Ok (parsed)
+}
+
+/// Database structure
+///
+/// # Members
+///
+///     ```
+///     pool: connections to the postgres server, each caching the
+///         statements prepared on it
+///     logger: reference to application logger
+///     ```
+pub struct Database {
+    pool: Pool<ConnectionManager>,
+    log: Arc<Log>
+}
+
+// Database implmentation
+impl Database {
+    /// connects to postgresql and constructs the database object around a
+    /// pool of at most pool_size connections. connections are checked before
+    /// each use and replaced if the server dropped them, so the bot
+    /// reconnects on its own once the server is back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let db = database::Database::construct(&config.database, &log).unwrap();"
+    /// ```
+    pub fn construct (settings: &DatabaseSettings, log: &Arc<Log>) -> Result<Self, Box<dyn Error>> {
+        let config = settings.postgres_config()?;
+        let tls = match config.get_ssl_mode() {
+            SslMode::Disable => None,
+            _ => Some(pool::tls_connector(settings.ca_file.as_deref())?)
+        };
+        let manager = ConnectionManager::construct(config, tls);
+        let pool = Pool::builder()
+            .max_size(settings.pool_size())
+            .min_idle(Some(1))
+            .test_on_check_out(true)
+            .connection_timeout(settings.pool_timeout())
+            .error_handler(Box::new(ErrorLogger { log: Arc::clone(log) }))
+            .build(manager)?;
+        Ok (
+            Self {
+                pool,
+                log: Arc::clone(log)
+            }
+        )
+    }
+    /// returns a connection from the pool, waiting for one to be returned or
+    /// for the server to accept a new one for at most pool_timeout.
+    fn connect (&self) -> Result<PooledConnection<ConnectionManager>, r2d2::Error> {
+        self.pool.get()
+    }
+    /// brings the database schema up to date with the binary by applying the
+    /// pending migrations and replacing the stored functions. a dry run
+    /// applies them in a transaction that is rolled back, which checks them
+    /// without changing the database.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::migrate(false).unwrap();"
+    /// ```
+    pub fn migrate (&self, dry_run: bool) -> Result <(), Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let pending = migrations::apply(&mut client, dry_run)?;
+        let action = if dry_run { "\tpending migration" } else { "\tapplied migration" };
+        for migration in pending.iter() {
+            info!(self.log.logger, "{}", action; "version" => migration.version, "description" => migration.description);
+        }
+        let version = migrations::MIGRATIONS.last().map(|m| m.version).unwrap_or_default();
+        if dry_run {
+            info!(self.log.logger, "dry run: rolled back database migrations"; "version" => version, "pending" => pending.len());
+        } else {
+            info!(self.log.logger, "\tdatabase schema is up to date"; "version" => version);
+        }
+        Ok (())
+    }
+    /// adds specified match making groups to the database for a given 
+    /// vector of groups. this is done by calling the add_matchmaking_groups()
+    /// stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: match making group already exists
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let groups: Vec<String> = Vec::new();
+    /// groups.push("1v1");
+    /// groups.push("3v3");
+    /// groups.push("6v6");
+    /// database::Database::add_mm_groups(groups).unwrap();"
+    /// ```
+    pub fn add_mm_groups (&self, groups: &[String]) -> Result <(), Box<dyn Error>> {
+        let mut client = self.connect()?;
+        for group in groups.iter() {
+            let statement = client.prepare_typed (
+                "SELECT add_match_making_group ( $1 );",
+                &[Type::TEXT]
+            )?;
+            let rows = client.query(&statement, &[&group])?;
+            let result: i32 = rows[0].get(0);
+            match result {
+                0 => info!(self.log.logger, "\tadded group"; "group" => group),
+                1 => warn!(self.log.logger, "\tgroup already exists in database"; "group" => group),
+                _ => return Err(format!("unknown database result for add_match_making_groups function: {}", result).into())
+            };
+        }
+        Ok (())
+    }
+    /// adds user to specified match making group in the database for a given 
+    /// discord uuid and group name. this is done by calling the add_match_making_user()
+    /// stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: failure to add user to database
+    ///     2: specified match making group does not exist
+    ///     3: user is already registered for this group
+    ///
+    /// #FIXME: We have to cast the u64 to a string here since the postgres lib can't
+    ///         convert a u64 to NUMERIC. Maybe there is a better way to do this.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::add_mm_user("uuid", "1v1").unwrap();"
+    /// ```
+    pub fn add_mm_user (&self, discord_uuid: u64, group: &str) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT add_match_making_user ( $1, $2 );",
+            &[Type::TEXT, Type::TEXT]
+        )?;
+        let rows = client.query(&statement, &[&discord_uuid.to_string(), &group])?;
+        Ok (rows[0].get(0))
+    }
+    /// puts a user popped from a match making group back into its queue,
+    /// keeping the time they originally subscribed so they keep their place.
+    /// this is done by calling the requeue_match_making_user() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: user was never registered for this group
+    ///     2: specified match making group does not exist
+    ///     3: user is already subscribed to this group
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::requeue_mm_user(discord_uuid, "3v3").unwrap();"
+    /// ```
+    pub fn requeue_mm_user (&self, discord_uuid: u64, group: &str) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT requeue_match_making_user ( $1, $2 );",
+            &[Type::TEXT, Type::TEXT]
+        )?;
+        let rows = client.query(&statement, &[&discord_uuid.to_string(), &group])?;
+        Ok (rows[0].get(0))
+    }
+    /// removes user from specified match making group in the database for a given 
+    /// discord uuid and group name. this is done by calling the remove_match_making_user()
+    /// stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: failure to add user to database
+    ///     2: specified match making group does not exist
+    ///     3: user is not registered for this group
+    ///
+    /// #FIXME: We have to cast the u64 to a string here since the postgres lib can't
+    ///         convert a u64 to NUMERIC. Maybe there is a better way to do this.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::remove_mm_user("uuid", "1v1").unwrap();"
+    /// ```
+    pub fn remove_mm_user (&self, discord_uuid: u64, group: &str) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT remove_match_making_user ( $1, $2 );",
+            &[Type::TEXT, Type::TEXT]
+        )?;
+        let rows = client.query(&statement, &[&discord_uuid.to_string(), &group])?;
+        Ok (rows[0].get(0))
+    }
+    /// removes user from every match making group they are subscribed to in the
+    /// database for a given discord uuid. this is done by calling the
+    /// clear_match_making_user() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: user does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::clear_mm_user("uuid").unwrap();"
+    /// ```
+    pub fn clear_mm_user (&self, discord_uuid: u64) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT clear_match_making_user ( $1 );",
+            &[Type::TEXT]
+        )?;
+        let rows = client.query(&statement, &[&discord_uuid.to_string()])?;
+        Ok (rows[0].get(0))
+    }
+    /// gets the discord uuids of every user subscribed to the specified match
+    /// making group in the order they subscribed. this is done by calling the
+    /// get_match_making_users() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let users = database::Database::get_mm_users("1v1").unwrap();"
+    /// ```
+    pub fn get_mm_users (&self, group: &str) -> Result <Vec<u64>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT discord_uuid FROM get_match_making_users ( $1 );",
+            &[Type::TEXT]
+        )?;
+        let mut users = Vec::new();
+        for row in client.query(&statement, &[&group])? {
+            let discord_uuid: String = row.get(0);
+            users.push(discord_uuid.parse::<u64>()?);
+        }
+        Ok (users)
+    }
+    /// gets the users currently subscribed to the specified match making
+    /// group along with the time they subscribed, longest waiting first. this
+    /// is done by calling the get_match_making_subscriptions() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let subscriptions = database::Database::get_mm_subscriptions("3v3").unwrap();"
+    /// ```
+    pub fn get_mm_subscriptions (&self, group: &str) -> Result <Vec<Subscription>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT discord_uuid, subscribed_at FROM get_match_making_subscriptions ( $1 );",
+            &[Type::TEXT]
+        )?;
+        let mut subscriptions = Vec::new();
+        for row in client.query(&statement, &[&group])? {
+            let discord_uuid: String = row.get(0);
+            subscriptions.push(Subscription {
+                discord_uuid: discord_uuid.parse::<u64>()?,
+                subscribed_at: row.get(1)
+            });
+        }
+        Ok (subscriptions)
+    }
+    /// gets the ratings of the specified players within a match making group.
+    /// this is done by calling the get_match_making_ratings() stored function.
+    /// players without a rating in the group are given the default rating.
+    /// ratings are returned in the same order as the players passed in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let players = database::Database::get_mm_ratings("3v3", &discord_uuids).unwrap();"
+    /// ```
+    pub fn get_mm_ratings (&self, group: &str, discord_uuids: &[u64]) -> Result <Vec<RatedPlayer>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT discord_uuid, rating FROM get_match_making_ratings ( $1, $2 );",
+            &[Type::TEXT, Type::TEXT_ARRAY]
+        )?;
+        let uuids: Vec<String> = discord_uuids.iter()
+            .map(|u| u.to_string())
+            .collect();
+        let rows = client.query(&statement, &[&group, &uuids])?;
+        let mut players = Vec::new();
+        for discord_uuid in discord_uuids.iter() {
+            let rating = rows.iter()
+                .find(|r| r.get::<_, String>(0) == discord_uuid.to_string())
+                .map(|r| r.get::<_, f64>(1))
+                .unwrap_or(rating::DEFAULT_RATING);
+            players.push(RatedPlayer {
+                discord_uuid: *discord_uuid,
+                rating
+            });
+        }
+        Ok (players)
+    }
+    /// adds a match for a popped match making group with the specified teams
+    /// and the map chosen by vote, if any, announced in the specified discord
+    /// channel. this is done by calling the add_match() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     >0: id of the new match
+    ///     -1: specified match making group does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let match_id = database::Database::add_match("3v3", &teams, Some("supply"), channel).unwrap();"
+    /// ```
+    pub fn add_match (&self, group: &str, teams: &Teams, map: Option<&str>, channel: u64) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT add_match ( $1, $2, $3, $4, $5 );",
+            &[Type::TEXT, Type::TEXT_ARRAY, Type::TEXT_ARRAY, Type::TEXT, Type::TEXT]
+        )?;
+        let axis: Vec<String> = teams.axis.iter()
+            .map(|p| p.discord_uuid.to_string())
+            .collect();
+        let allies: Vec<String> = teams.allies.iter()
+            .map(|p| p.discord_uuid.to_string())
+            .collect();
+        let rows = client.query(&statement, &[&group, &axis, &allies, &map, &channel.to_string()])?;
+        let result: i32 = rows[0].get(0);
+        if result < 0 {
+            return Err(format!("match making group: {} does not exist", group).into());
+        }
+        Ok (result)
+    }
+    /// gets the players of a match split into their teams with their current
+    /// rating within the group of the match. this is done by calling the
+    /// get_match_players() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let teams = database::Database::get_match_players(match_id).unwrap();"
+    /// ```
+    pub fn get_match_players (&self, match_id: i32) -> Result <Teams, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT discord_uuid, team, rating FROM get_match_players ( $1 );",
+            &[Type::INT4]
+        )?;
+        let mut teams = Teams {
+            axis: Vec::new(),
+            allies: Vec::new()
+        };
+        for row in client.query(&statement, &[&match_id])? {
+            let discord_uuid: String = row.get(0);
+            let team: String = row.get(1);
+            let player = RatedPlayer {
+                discord_uuid: discord_uuid.parse::<u64>()?,
+                rating: row.get(2)
+            };
+            match Team::parse(&team)? {
+                Team::Axis => teams.axis.push(player),
+                Team::Allies => teams.allies.push(player)
+            };
+        }
+        if teams.axis.is_empty() && teams.allies.is_empty() {
+            return Err(format!("match: {} does not exist", match_id).into());
+        }
+        Ok (teams)
+    }
+    /// completes a match by storing its result and the new ratings of its
+    /// players. this is done by calling the complete_match() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: match does not exist
+    ///     2: match is already completed or cancelled
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::complete_match(match_id, MatchResult::Draw, &updated_teams).unwrap();"
+    /// ```
+    pub fn complete_match (&self, match_id: i32, result: MatchResult, teams: &Teams) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT complete_match ( $1, $2, $3, $4 );",
+            &[Type::INT4, Type::TEXT, Type::TEXT_ARRAY, Type::FLOAT8_ARRAY]
+        )?;
+        let players: Vec<&RatedPlayer> = teams.axis.iter()
+            .chain(teams.allies.iter())
+            .collect();
+        let discord_uuids: Vec<String> = players.iter()
+            .map(|p| p.discord_uuid.to_string())
+            .collect();
+        let ratings: Vec<f64> = players.iter()
+            .map(|p| p.rating)
+            .collect();
+        let rows = client.query(&statement, &[&match_id, &result.as_str(), &discord_uuids, &ratings])?;
+        Ok (rows[0].get(0))
+    }
+    /// marks a match as disputed so that its result can be decided by an
+    /// admin. this is done by calling the dispute_match() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: match does not exist
+    ///     2: match is already completed or cancelled
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::dispute_match(match_id).unwrap();"
+    /// ```
+    pub fn dispute_match (&self, match_id: i32) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT dispute_match ( $1 );",
+            &[Type::INT4]
+        )?;
+        let rows = client.query(&statement, &[&match_id])?;
+        Ok (rows[0].get(0))
+    }
+    /// cancels a match without a result, leaving the ratings of its players as
+    /// they are. this is done by calling the cancel_match() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: match does not exist
+    ///     2: match is already completed or cancelled
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::cancel_match(match_id).unwrap();"
+    /// ```
+    pub fn cancel_match (&self, match_id: i32) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT cancel_match ( $1 );",
+            &[Type::INT4]
+        )?;
+        let rows = client.query(&statement, &[&match_id])?;
+        Ok (rows[0].get(0))
+    }
+    /// adjusts the rating of a user within a match making group by the
+    /// specified amount. this is done by calling the adjust_rating() stored
+    /// function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: match making group does not exist
+    ///     2: user has never joined the match making group
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::adjust_rating(discord_uuid, "6v6", -50.0).unwrap();"
+    /// ```
+    pub fn adjust_rating (&self, discord_uuid: u64, group: &str, amount: f64) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT adjust_rating ( $1, $2, $3 );",
+            &[Type::TEXT, Type::TEXT, Type::FLOAT8]
+        )?;
+        let rows = client.query(&statement, &[&discord_uuid.to_string(), &group, &amount])?;
+        Ok (rows[0].get(0))
+    }
+    /// gets every match whose result is disputed. this is done by calling the
+    /// get_disputed_matches() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let disputes = database::Database::get_disputed_matches().unwrap();"
+    /// ```
+    pub fn get_disputed_matches (&self) -> Result <Vec<DisputedMatch>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare(
+            "SELECT match_id, group_name, created_at FROM get_disputed_matches ();"
+        )?;
+        let mut disputes = Vec::new();
+        for row in client.query(&statement, &[])? {
+            disputes.push(DisputedMatch {
+                match_id: row.get(0),
+                group: row.get(1),
+                created_at: row.get(2)
+            });
+        }
+        Ok (disputes)
+    }
+    /// gets the maps most recently played in a match making group, ordered
+    /// from most to least recent. this is done by calling the get_recent_maps()
+    /// stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let recent = database::Database::get_recent_maps("6v6", 2).unwrap();"
+    /// ```
+    pub fn get_recent_maps (&self, group: &str, count: usize) -> Result <Vec<String>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT map FROM get_recent_maps ( $1, $2 );",
+            &[Type::TEXT, Type::INT4]
+        )?;
+        let mut maps = Vec::new();
+        for row in client.query(&statement, &[&group, &(count as i32)])? {
+            maps.push(row.get(0));
+        }
+        Ok (maps)
+    }
+    /// links an in-game identity to a user. this is done by calling the
+    /// link_user_identity() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: failure to add user to database
+    ///     2: identity is linked to another user
+    ///     3: identity is already linked to this user
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::link_user_identity(discord_uuid, &Identity::Guid(guid)).unwrap();"
+    /// ```
+    pub fn link_user_identity (&self, discord_uuid: u64, identity: &Identity) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT link_user_identity ( $1, $2, $3 );",
+            &[Type::TEXT, Type::TEXT, Type::TEXT]
+        )?;
+        let rows = client.query(&statement, &[&discord_uuid.to_string(), &identity.kind(), &identity.value()])?;
+        Ok (rows[0].get(0))
+    }
+    /// gets the user an in-game identity is linked to, if any. this is done by
+    /// calling the get_linked_user() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let user = database::Database::get_linked_user(&Identity::Guid(guid)).unwrap();"
+    /// ```
+    pub fn get_linked_user (&self, identity: &Identity) -> Result <Option<u64>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT get_linked_user ( $1, $2 );",
+            &[Type::TEXT, Type::TEXT]
+        )?;
+        let rows = client.query(&statement, &[&identity.kind(), &identity.value()])?;
+        let discord_uuid: Option<String> = rows[0].get(0);
+        match discord_uuid {
+            Some(u) => Ok (Some(u.parse()?)),
+            None => Ok (None)
+        }
+    }
+    /// bans a user from queueing for an offence until the specified time.
+    /// this is done by calling the add_penalty() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: failure to add user to database
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::add_penalty(discord_uuid, Offence::MissedReadyCheck, expires_at).unwrap();"
+    /// ```
+    pub fn add_penalty (&self, discord_uuid: u64, offence: Offence, expires_at: SystemTime) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT add_penalty ( $1, $2, $3 );",
+            &[Type::TEXT, Type::TEXT, Type::TIMESTAMPTZ]
+        )?;
+        let rows = client.query(&statement, &[&discord_uuid.to_string(), &offence.as_str(), &expires_at])?;
+        Ok (rows[0].get(0))
+    }
+    /// gets every queue ban given to a user, oldest first. this is done by
+    /// calling the get_penalties() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let penalties = database::Database::get_penalties(discord_uuid).unwrap();"
+    /// ```
+    pub fn get_penalties (&self, discord_uuid: u64) -> Result <Vec<Penalty>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT offence, created_at, expires_at FROM get_penalties ( $1 );",
+            &[Type::TEXT]
+        )?;
+        let mut penalties = Vec::new();
+        for row in client.query(&statement, &[&discord_uuid.to_string()])? {
+            let offence: String = row.get(0);
+            penalties.push(Penalty {
+                offence: Offence::parse(&offence)?,
+                created_at: row.get(1),
+                expires_at: row.get(2)
+            });
+        }
+        Ok (penalties)
+    }
+    /// lifts every queue ban of a user that has not expired yet, returning the
+    /// number of bans lifted. this is done by calling the lift_penalties()
+    /// stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let lifted = database::Database::lift_penalties(discord_uuid).unwrap();"
+    /// ```
+    pub fn lift_penalties (&self, discord_uuid: u64) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT lift_penalties ( $1 );",
+            &[Type::TEXT]
+        )?;
+        let rows = client.query(&statement, &[&discord_uuid.to_string()])?;
+        Ok (rows[0].get(0))
+    }
+    /// adds the statistics of a player in a match, identified by their linked
+    /// in-game identity. this is done by calling the add_player_stats() stored
+    /// function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: match does not exist
+    ///     2: identity is not linked to a user
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::add_player_stats(match_id, &identity, &stats).unwrap();"
+    /// ```
+    pub fn add_player_stats (&self, match_id: i32, identity: &Identity, stats: &PlayerStats) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT add_player_stats ( $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15 );",
+            &[
+                Type::INT4, Type::TEXT, Type::TEXT,
+                Type::INT4, Type::INT4, Type::INT4, Type::INT4, Type::INT4, Type::INT4, Type::INT4,
+                Type::INT4_ARRAY, Type::INT4_ARRAY, Type::INT4_ARRAY, Type::INT4_ARRAY, Type::INT4_ARRAY
+            ]
+        )?;
+        let mut weapons: Vec<i32> = Vec::new();
+        let mut hits: Vec<i32> = Vec::new();
+        let mut shots: Vec<i32> = Vec::new();
+        let mut kills: Vec<i32> = Vec::new();
+        let mut headshots: Vec<i32> = Vec::new();
+        for (weapon, totals) in stats.weapons.iter() {
+            weapons.push(*weapon as i32);
+            hits.push(totals.hits as i32);
+            shots.push(totals.shots as i32);
+            kills.push(totals.kills as i32);
+            headshots.push(totals.headshots as i32);
+        }
+        let rows = client.query(&statement, &[
+            &match_id, &identity.kind(), &identity.value(),
+            &(stats.kills as i32), &(stats.deaths as i32), &(stats.gibs as i32), &(stats.revives as i32),
+            &(stats.damage_given as i32), &(stats.damage_received as i32), &(stats.objectives as i32),
+            &weapons, &hits, &shots, &kills, &headshots
+        ])?;
+        Ok (rows[0].get(0))
+    }
+    /// gets the lifetime statistics of a user over every completed match,
+    /// optionally limited to a single match making group. this is done by
+    /// calling the get_player_stats() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let stats = database::Database::get_player_stats(discord_uuid, Some("6v6")).unwrap();"
+    /// ```
+    pub fn get_player_stats (&self, discord_uuid: u64, group: Option<&str>) -> Result <LifetimeStats, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT * FROM get_player_stats ( $1, $2 );",
+            &[Type::TEXT, Type::TEXT]
+        )?;
+        let rows = client.query(&statement, &[&discord_uuid.to_string(), &group])?;
+        Ok (
+            LifetimeStats {
+                matches: rows[0].get(0),
+                wins: rows[0].get(1),
+                losses: rows[0].get(2),
+                draws: rows[0].get(3),
+                kills: rows[0].get(4),
+                deaths: rows[0].get(5),
+                gibs: rows[0].get(6),
+                revives: rows[0].get(7),
+                damage_given: rows[0].get(8),
+                damage_received: rows[0].get(9),
+                objectives: rows[0].get(10),
+                hits: rows[0].get(11),
+                shots: rows[0].get(12),
+                headshots: rows[0].get(13)
+            }
+        )
+    }
+    /// gets the most recent completed matches of a user, optionally limited
+    /// to a single match making group, ordered from most to least recent.
+    /// this is done by calling the get_recent_player_matches() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let recent = database::Database::get_recent_player_matches(discord_uuid, None, 5).unwrap();"
+    /// ```
+    pub fn get_recent_player_matches (&self, discord_uuid: u64, group: Option<&str>, count: usize) -> Result <Vec<RecentMatch>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT match_id, outcome, kills, deaths FROM get_recent_player_matches ( $1, $2, $3 );",
+            &[Type::TEXT, Type::TEXT, Type::INT4]
+        )?;
+        let mut matches = Vec::new();
+        for row in client.query(&statement, &[&discord_uuid.to_string(), &group, &(count as i32)])? {
+            matches.push(RecentMatch {
+                match_id: row.get(0),
+                outcome: row.get(1),
+                kills: row.get(2),
+                deaths: row.get(3)
+            });
+        }
+        Ok (matches)
+    }
+    /// gets a page of the leaderboard of a match making group along with the
+    /// number of ranked players. players with fewer completed matches than
+    /// the minimum are left out as provisional. this is done by calling the
+    /// get_leaderboard() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let (standings, ranked) = database::Database::get_leaderboard("6v6", 5, 10, 0).unwrap();"
+    /// ```
+    pub fn get_leaderboard (&self, group: &str, min_games: usize, page_size: usize, offset: usize) -> Result <(Vec<Standing>, i64), Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT * FROM get_leaderboard ( $1, $2, $3, $4 );",
+            &[Type::TEXT, Type::INT4, Type::INT4, Type::INT4]
+        )?;
+        let rows = client.query(&statement, &[&group, &(min_games as i32), &(page_size as i32), &(offset as i32)])?;
+        standings(&rows)
+    }
+    /// gets a page of the archived leaderboard of an ended season of a match
+    /// making group along with the number of ranked players. this is done by
+    /// calling the get_season_standings() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let (standings, ranked) = database::Database::get_season_standings("6v6", 2, 10, 0).unwrap();"
+    /// ```
+    pub fn get_season_standings (&self, group: &str, season: i32, page_size: usize, offset: usize) -> Result <(Vec<Standing>, i64), Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT * FROM get_season_standings ( $1, $2, $3, $4 );",
+            &[Type::TEXT, Type::INT4, Type::INT4, Type::INT4]
+        )?;
+        let rows = client.query(&statement, &[&group, &season, &(page_size as i32), &(offset as i32)])?;
+        standings(&rows)
+    }
+    /// starts a new season in a match making group, moving every rating in
+    /// the group towards the mean. this is done by calling the start_season()
+    /// stored function.
+    ///
+    /// the stored function returns the following:
+    ///     >0: number of the new season
+    ///     -1: specified match making group does not exist
+    ///     -2: a season is already running in the group
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let season = database::Database::start_season("6v6").unwrap();"
+    /// ```
+    pub fn start_season (&self, group: &str) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT start_season ( $1, $2 );",
+            &[Type::TEXT, Type::FLOAT8]
+        )?;
+        let rows = client.query(&statement, &[&group, &rating::SEASON_RESET])?;
+        let result: i32 = rows[0].get(0);
+        match result {
+            -1 => Err(format!("match making group: {} does not exist", group).into()),
+            -2 => Err(format!("a season is already running in `{}`.", group).into()),
+            r if r > 0 => Ok (r),
+            _ => Err(format!("unknown database result for start_season function: {}", result).into())
+        }
+    }
+    /// ends the running season of a match making group, archiving its final
+    /// leaderboard. players with fewer completed matches than the minimum are
+    /// left out of the archive. this is done by calling the end_season()
+    /// stored function.
+    ///
+    /// the stored function returns the following:
+    ///     >0: number of the ended season
+    ///     -1: specified match making group does not exist
+    ///     -2: no season is running in the group
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let season = database::Database::end_season("6v6", 5).unwrap();"
+    /// ```
+    pub fn end_season (&self, group: &str, min_games: usize) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT end_season ( $1, $2 );",
+            &[Type::TEXT, Type::INT4]
+        )?;
+        let rows = client.query(&statement, &[&group, &(min_games as i32)])?;
+        let result: i32 = rows[0].get(0);
+        match result {
+            -1 => Err(format!("match making group: {} does not exist", group).into()),
+            -2 => Err(format!("no season is running in `{}`.", group).into()),
+            r if r > 0 => Ok (r),
+            _ => Err(format!("unknown database result for end_season function: {}", result).into())
+        }
+    }
+    /// adds specified game servers to the database for a given vector of
+    /// server names. this is done by calling the add_server() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: server already exists
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::add_servers(&["eu1".to_string()]).unwrap();"
+    /// ```
+    pub fn add_servers (&self, servers: &[String]) -> Result <(), Box<dyn Error>> {
+        let mut client = self.connect()?;
+        for server in servers.iter() {
+            let statement = client.prepare_typed (
+                "SELECT add_server ( $1 );",
+                &[Type::TEXT]
+            )?;
+            let rows = client.query(&statement, &[&server])?;
+            let result: i32 = rows[0].get(0);
+            match result {
+                0 => info!(self.log.logger, "\tadded server"; "server" => server),
+                1 => warn!(self.log.logger, "\tserver already exists in database"; "server" => server),
+                _ => return Err(format!("unknown database result for add_server function: {}", result).into())
+            };
+        }
+        Ok (())
+    }
+    /// allocates a free game server to a match. this is done by calling the
+    /// allocate_server() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: server does not exist
+    ///     2: server is allocated to another match
+    ///     3: match does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::allocate_server("eu1", match_id).unwrap();"
+    /// ```
+    pub fn allocate_server (&self, server: &str, match_id: i32) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT allocate_server ( $1, $2 );",
+            &[Type::TEXT, Type::INT4]
+        )?;
+        let rows = client.query(&statement, &[&server, &match_id])?;
+        Ok (rows[0].get(0))
+    }
+    /// releases the game server allocated to a match. this is done by calling
+    /// the release_server() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: no server is allocated to the match
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::release_server(match_id).unwrap();"
+    /// ```
+    pub fn release_server (&self, match_id: i32) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT release_server ( $1 );",
+            &[Type::INT4]
+        )?;
+        let rows = client.query(&statement, &[&match_id])?;
+        Ok (rows[0].get(0))
+    }
+    /// gets every game server that is allocated to a match along with the id
+    /// of that match. this is done by calling the get_busy_servers() stored
+    /// function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let busy = database::Database::get_busy_servers().unwrap();"
+    /// ```
+    pub fn get_busy_servers (&self) -> Result <HashMap<String, i32>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare(
+            "SELECT server_name, match_id FROM get_busy_servers ();"
+        )?;
+        let mut servers = HashMap::new();
+        for row in client.query(&statement, &[])? {
+            servers.insert(row.get(0), row.get(1));
+        }
+        Ok (servers)
+    }
+    /// stores a ready check so it survives a restart of the bot, adding it
+    /// if it has no id yet. this is done by calling the save_ready_check()
+    /// stored function.
+    ///
+    /// the stored function returns the following:
+    ///     >0: id of the ready check
+    ///     -1: specified match making group does not exist
+    ///     -2: ready check does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// check.id = Some(database::Database::save_ready_check(&check).unwrap());"
+    /// ```
+    pub fn save_ready_check (&self, check: &ReadyCheck) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT save_ready_check ( $1, $2, $3, $4, $5, $6, $7 );",
+            &[Type::INT4, Type::TEXT, Type::TEXT, Type::TEXT, Type::TEXT_ARRAY, Type::TEXT_ARRAY, Type::TIMESTAMPTZ]
+        )?;
+        let rows = client.query(&statement, &[
+            &check.id,
+            &check.group,
+            &check.channel.to_string(),
+            &check.message.map(|m| m.to_string()),
+            &uuid_strings(check.players()),
+            &uuid_strings(&check.ready_players()),
+            &check.deadline
+        ])?;
+        let result: i32 = rows[0].get(0);
+        match result {
+            -1 => Err(format!("match making group: {} does not exist", check.group).into()),
+            -2 => Err(format!("ready check: {} does not exist", check.id.unwrap_or_default()).into()),
+            id => Ok (id)
+        }
+    }
+    /// removes a ready check that completed or ran out of time. this is done
+    /// by calling the remove_ready_check() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: ready check does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::remove_ready_check(id).unwrap();"
+    /// ```
+    pub fn remove_ready_check (&self, id: i32) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT remove_ready_check ( $1 );",
+            &[Type::INT4]
+        )?;
+        let rows = client.query(&statement, &[&id])?;
+        Ok (rows[0].get(0))
+    }
+    /// gets every stored ready check that is waiting on its players. this is
+    /// done by calling the get_ready_checks() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let checks = database::Database::get_ready_checks().unwrap();"
+    /// ```
+    pub fn get_ready_checks (&self) -> Result <Vec<ReadyCheck>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare(
+            "SELECT ready_check_id, group_name, channel_id, message_id, players, ready, deadline FROM get_ready_checks ();"
+        )?;
+        let mut checks = Vec::new();
+        for row in client.query(&statement, &[])? {
+            let group: String = row.get(1);
+            let channel: String = row.get(2);
+            let message: Option<String> = row.get(3);
+            let players: Vec<String> = row.get(4);
+            let ready: Vec<String> = row.get(5);
+            let mut check = ReadyCheck::construct(&group, channel.parse()?, &parse_uuids(&players)?, Duration::from_secs(0));
+            check.id = Some(row.get(0));
+            check.message = match message {
+                Some(m) => Some(m.parse()?),
+                None => None
+            };
+            check.deadline = row.get(6);
+            for player in parse_uuids(&ready)?.iter() {
+                check.ready(*player);
+            }
+            checks.push(check);
+        }
+        Ok (checks)
+    }
+    /// stores a map vote so it survives a restart of the bot, adding it if it
+    /// has no id yet. this is done by calling the save_map_vote() stored
+    /// function.
+    ///
+    /// the stored function returns the following:
+    ///     >0: id of the map vote
+    ///     -1: specified match making group does not exist
+    ///     -2: map vote does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// vote.id = Some(database::Database::save_map_vote(&vote).unwrap());"
+    /// ```
+    pub fn save_map_vote (&self, vote: &MapVote) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT save_map_vote ( $1, $2, $3, $4, $5, $6, $7, $8, $9 );",
+            &[
+                Type::INT4, Type::TEXT, Type::TEXT, Type::TEXT, Type::TEXT_ARRAY,
+                Type::TEXT_ARRAY, Type::TEXT_ARRAY, Type::INT4_ARRAY, Type::TIMESTAMPTZ
+            ]
+        )?;
+        let voters: Vec<u64> = vote.votes().keys().cloned().collect();
+        let choices: Vec<i32> = voters.iter()
+            .map(|v| vote.votes()[v] as i32)
+            .collect();
+        let rows = client.query(&statement, &[
+            &vote.id,
+            &vote.group,
+            &vote.channel.to_string(),
+            &vote.message.map(|m| m.to_string()),
+            &uuid_strings(vote.players()),
+            &vote.maps(),
+            &uuid_strings(&voters),
+            &choices,
+            &vote.deadline
+        ])?;
+        let result: i32 = rows[0].get(0);
+        match result {
+            -1 => Err(format!("match making group: {} does not exist", vote.group).into()),
+            -2 => Err(format!("map vote: {} does not exist", vote.id.unwrap_or_default()).into()),
+            id => Ok (id)
+        }
+    }
+    /// removes a map vote that is over. this is done by calling the
+    /// remove_map_vote() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: map vote does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::remove_map_vote(id).unwrap();"
+    /// ```
+    pub fn remove_map_vote (&self, id: i32) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT remove_map_vote ( $1 );",
+            &[Type::INT4]
+        )?;
+        let rows = client.query(&statement, &[&id])?;
+        Ok (rows[0].get(0))
+    }
+    /// gets every stored map vote in progress. this is done by calling the
+    /// get_map_votes() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let votes = database::Database::get_map_votes().unwrap();"
+    /// ```
+    pub fn get_map_votes (&self) -> Result <Vec<MapVote>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare(
+            "SELECT map_vote_id, group_name, channel_id, message_id, players, maps, voters, choices, deadline FROM get_map_votes ();"
+        )?;
+        let mut votes = Vec::new();
+        for row in client.query(&statement, &[])? {
+            let group: String = row.get(1);
+            let channel: String = row.get(2);
+            let message: Option<String> = row.get(3);
+            let players: Vec<String> = row.get(4);
+            let maps: Vec<String> = row.get(5);
+            let voters: Vec<String> = row.get(6);
+            let choices: Vec<i32> = row.get(7);
+            let mut vote = MapVote::construct(&group, channel.parse()?, &parse_uuids(&players)?, &maps, Duration::from_secs(0));
+            vote.id = Some(row.get(0));
+            vote.message = match message {
+                Some(m) => Some(m.parse()?),
+                None => None
+            };
+            vote.deadline = row.get(8);
+            for (voter, choice) in parse_uuids(&voters)?.iter().zip(choices.iter()) {
+                vote.vote_index(*voter, *choice as usize)?;
+            }
+            votes.push(vote);
+        }
+        Ok (votes)
+    }
+    /// stores a captain draft so it survives a restart of the bot, adding it
+    /// if it has no id yet. this is done by calling the save_draft() stored
+    /// function.
+    ///
+    /// the stored function returns the following:
+    ///     >0: id of the draft
+    ///     -1: specified match making group does not exist
+    ///     -2: draft does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// draft.id = Some(database::Database::save_draft(&draft).unwrap());"
+    /// ```
+    pub fn save_draft (&self, draft: &Draft) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT save_draft ( $1, $2, $3, $4, $5, $6, $7, $8 );",
+            &[
+                Type::INT4, Type::TEXT, Type::TEXT, Type::TEXT,
+                Type::TEXT_ARRAY, Type::TEXT_ARRAY, Type::TEXT_ARRAY, Type::TIMESTAMPTZ
+            ]
+        )?;
+        let uuids = |players: &[RatedPlayer]| -> Vec<String> {
+            players.iter()
+                .map(|p| p.discord_uuid.to_string())
+                .collect()
+        };
+        let rows = client.query(&statement, &[
+            &draft.id,
+            &draft.group,
+            &draft.channel.to_string(),
+            &draft.map,
+            &uuids(&draft.teams.axis),
+            &uuids(&draft.teams.allies),
+            &uuids(draft.pool()),
+            &draft.deadline
+        ])?;
+        let result: i32 = rows[0].get(0);
+        match result {
+            -1 => Err(format!("match making group: {} does not exist", draft.group).into()),
+            -2 => Err(format!("draft: {} does not exist", draft.id.unwrap_or_default()).into()),
+            id => Ok (id)
+        }
+    }
+    /// removes a captain draft that completed. this is done by calling the
+    /// remove_draft() stored function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: draft does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::remove_draft(id).unwrap();"
+    /// ```
+    pub fn remove_draft (&self, id: i32) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT remove_draft ( $1 );",
+            &[Type::INT4]
+        )?;
+        let rows = client.query(&statement, &[&id])?;
+        Ok (rows[0].get(0))
+    }
+    /// gets every stored captain draft in progress with the current ratings of
+    /// its players, giving captains the specified time for each pick. this is
+    /// done by calling the get_drafts() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let drafts = database::Database::get_drafts(Duration::from_secs(30)).unwrap();"
+    /// ```
+    pub fn get_drafts (&self, timeout: Duration) -> Result <Vec<Draft>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare(
+            "SELECT draft_id, group_name, channel_id, map, axis, allies, pool, deadline FROM get_drafts ();"
+        )?;
+        let mut drafts = Vec::new();
+        for row in client.query(&statement, &[])? {
+            let group: String = row.get(1);
+            let channel: String = row.get(2);
+            let axis: Vec<String> = row.get(4);
+            let allies: Vec<String> = row.get(5);
+            let pool: Vec<String> = row.get(6);
+            let (axis, allies, pool) = (parse_uuids(&axis)?, parse_uuids(&allies)?, parse_uuids(&pool)?);
+            let players: Vec<u64> = axis.iter()
+                .chain(allies.iter())
+                .chain(pool.iter())
+                .cloned()
+                .collect();
+            let ratings = self.get_mm_ratings(&group, &players)?;
+            let rated = |uuids: &[u64]| -> Vec<RatedPlayer> {
+                uuids.iter()
+                    .filter_map(|u| ratings.iter().find(|r| r.discord_uuid == *u))
+                    .cloned()
+                    .collect()
+            };
+            let teams = Teams {
+                axis: rated(&axis),
+                allies: rated(&allies)
+            };
+            let mut draft = Draft::restore(&group, channel.parse()?, &teams, &rated(&pool), timeout)?;
+            draft.id = Some(row.get(0));
+            draft.map = row.get(3);
+            draft.deadline = row.get(7);
+            drafts.push(draft);
+        }
+        Ok (drafts)
+    }
+    /// stores whether a match in progress went live and the result each team
+    /// reported so far. this is done by calling the save_match_state() stored
+    /// function.
+    ///
+    /// the stored function returns the following:
+    ///     0: success
+    ///     1: match does not exist or is no longer in progress
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// database::Database::save_match_state(&game).unwrap();"
+    /// ```
+    pub fn save_match_state (&self, game: &Match) -> Result <i32, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare_typed (
+            "SELECT save_match_state ( $1, $2, $3, $4 );",
+            &[Type::INT4, Type::BOOL, Type::TEXT, Type::TEXT]
+        )?;
+        let report = |team: Team| game.reports.get(&team).map(|r| r.as_str());
+        let rows = client.query(&statement, &[&game.id, &game.live, &report(Team::Axis), &report(Team::Allies)])?;
+        Ok (rows[0].get(0))
+    }
+    /// gets every match that is still in progress with its players, game
+    /// server and stored state. this is done by calling the
+    /// get_active_matches() stored function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let matches = database::Database::get_active_matches().unwrap();"
+    /// ```
+    pub fn get_active_matches (&self) -> Result <Vec<Match>, Box<dyn Error>> {
+        let mut client = self.connect()?;
+        let statement = client.prepare(
+            "SELECT match_id, group_name, channel_id, server_name, live, axis_report, allies_report FROM get_active_matches ();"
+        )?;
+        let rows = client.query(&statement, &[])?;
+        // the players of each match are fetched on their own connection, so
+        // this one goes back to the pool first
+        drop(client);
+        let mut matches = Vec::new();
+        for row in rows {
+            let match_id: i32 = row.get(0);
+            let group: String = row.get(1);
+            let channel: Option<String> = row.get(2);
+            let channel = match channel {
+                Some(c) => c.parse()?,
+                None => 0
+            };
+            let mut game = Match::construct(match_id, &group, channel, self.get_match_players(match_id)?);
+            game.server = row.get(3);
+            game.live = row.get(4);
+            for (team, column) in [(Team::Axis, 5), (Team::Allies, 6)].iter() {
+                let report: Option<String> = row.get(*column);
+                if let Some(r) = report {
+                    game.reports.insert(*team, MatchResult::parse(&r)?);
+                }
+            }
+            matches.push(game);
+        }
+        Ok (matches)
+    }
+}
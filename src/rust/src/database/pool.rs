@@ -0,0 +1,195 @@
+use crate::logger::Log;
+use postgres:: {
+    Client,
+    Config,
+    NoTls,
+    Statement,
+    types::Type
+};
+use r2d2:: {
+    HandleError,
+    ManageConnection
+};
+use rustls:: {
+    ClientConfig,
+    RootCertStore
+};
+use std:: {
+    collections::HashMap,
+    error::Error,
+    fmt,
+    fs::File,
+    io::BufReader,
+    ops:: {
+        Deref,
+        DerefMut
+    },
+    sync::Arc
+};
+use tokio_postgres_rustls::MakeRustlsConnect;
+
+/// returns the tls connector for the postgres server, trusting the
+/// certificate authorities of the specified pem file or the webpki roots.
+/// it is only built if the configured sslmode asks for tls, since the
+/// connector refuses unix sockets even when tls is disabled.
+pub fn tls_connector (ca_file: Option<&str>) -> Result<MakeRustlsConnect, Box<dyn Error>> {
+    let mut roots = RootCertStore::empty();
+    match ca_file {
+        Some(path) => {
+            let file = match File::open(path) {
+                Ok (f) => f,
+                Err(e) => return Err(format!("database information: cannot read ca file {}: {}", path, e).into())
+            };
+            for certificate in rustls_pemfile::certs(&mut BufReader::new(file)) {
+                roots.add(certificate?)?;
+            }
+            if roots.is_empty() {
+                return Err(format!("database information: no certificates in ca file {}", path).into());
+            }
+        },
+        None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned())
+    };
+    let config = ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok (MakeRustlsConnect::new(config))
+}
+
+/// returns the message of a postgres error, which is that of the server if
+/// the server raised it.
+pub fn describe (error: &postgres::Error) -> String {
+    match error.as_db_error() {
+        Some(e) => e.to_string(),
+        None => error.to_string()
+    }
+}
+
+/// Connection structure for a pooled postgres connection
+///
+/// # Members
+///
+///     ```
+///     client: postgres client of the connection
+///     statements: statements prepared on the connection, by query
+///     ```
+pub struct Connection {
+    client: Client,
+    statements: HashMap<String, Statement>
+}
+
+// Connection implementation
+impl Connection {
+    /// returns the prepared statement for a query, preparing it on the first
+    /// use of the query on this connection.
+    pub fn prepare (&mut self, query: &str) -> Result<Statement, postgres::Error> {
+        self.prepare_typed(query, &[])
+    }
+    /// returns the prepared statement for a query with the specified
+    /// parameter types, preparing it on the first use of the query on this
+    /// connection.
+    pub fn prepare_typed (&mut self, query: &str, types: &[Type]) -> Result<Statement, postgres::Error> {
+        if let Some(s) = self.statements.get(query) {
+            return Ok (s.clone());
+        }
+        let statement = self.client.prepare_typed(query, types)?;
+        self.statements.insert(query.to_string(), statement.clone());
+        Ok (statement)
+    }
+}
+
+// Deref implementation for Connection
+impl Deref for Connection {
+    type Target = Client;
+
+    fn deref (&self) -> &Client {
+        &self.client
+    }
+}
+
+// DerefMut implementation for Connection
+impl DerefMut for Connection {
+    fn deref_mut (&mut self) -> &mut Client {
+        &mut self.client
+    }
+}
+
+/// ConnectionManager structure opening and checking the connections of the
+/// pool
+///
+/// # Members
+///
+///     ```
+///     config: settings for connecting to the postgres server
+///     tls: connector encrypting the connection, if the settings ask for it
+///     ```
+pub struct ConnectionManager {
+    config: Config,
+    tls: Option<MakeRustlsConnect>
+}
+
+// ConnectionManager implementation
+impl ConnectionManager {
+    /// constructs the connection manager from the postgres settings and the
+    /// tls connector, if any.
+    pub fn construct (config: Config, tls: Option<MakeRustlsConnect>) -> Self {
+        Self {
+            config,
+            tls
+        }
+    }
+}
+
+// ManageConnection implementation for ConnectionManager
+impl ManageConnection for ConnectionManager {
+    type Connection = Connection;
+    type Error = postgres::Error;
+
+    fn connect (&self) -> Result<Connection, postgres::Error> {
+        let client = match &self.tls {
+            Some(t) => self.config.connect(t.clone())?,
+            None => self.config.connect(NoTls)?
+        };
+        Ok (
+            Connection {
+                client,
+                statements: HashMap::new()
+            }
+        )
+    }
+
+    fn is_valid (&self, connection: &mut Connection) -> Result<(), postgres::Error> {
+        connection.client.simple_query("")?;
+        Ok (())
+    }
+
+    fn has_broken (&self, connection: &mut Connection) -> bool {
+        connection.client.is_closed()
+    }
+}
+
+/// ErrorLogger structure writing the errors of the pool to the application
+/// log, such as failing to reconnect to the postgres server
+///
+/// # Members
+///
+///     ```
+///     log: reference to application logger
+///     ```
+pub struct ErrorLogger {
+    pub log: Arc<Log>
+}
+
+// Debug implementation for ErrorLogger
+impl fmt::Debug for ErrorLogger {
+    fn fmt (&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ErrorLogger")
+    }
+}
+
+// HandleError implementation for ErrorLogger
+impl HandleError<postgres::Error> for ErrorLogger {
+    fn handle_error (&self, error: postgres::Error) {
+        warn!(self.log.logger, "database connection failed"; "error" => describe(&error));
+    }
+}
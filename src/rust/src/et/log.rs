@@ -0,0 +1,365 @@
+use crate::et::query;
+use std:: {
+    collections::HashMap,
+    fs::File,
+    io:: {
+        self,
+        prelude::*,
+        SeekFrom
+    },
+    path::PathBuf
+};
+
+/// number of values logged for every weapon in a WeaponStats line.
+const WEAPON_FIELDS: usize = 5;
+
+/// Team enumeration for the sides named in ET log lines
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Team {
+    Axis,
+    Allies
+}
+
+/// WeaponStat structure for the accuracy of a player with a single weapon
+///
+/// # Members
+///
+/// ```text
+/// weapon: index of the weapon in the weapon mask of the line
+/// hits: shots that hit an enemy
+/// shots: shots fired
+/// kills: kills made with the weapon
+/// deaths: deaths while holding the weapon
+/// headshots: hits to the head
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeaponStat {
+    pub weapon: u32,
+    pub hits: u32,
+    pub shots: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub headshots: u32
+}
+
+/// Event enumeration for the lines of an ET server log the bot cares about
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// a map started with the specified server info
+    InitGame(HashMap<String, String>),
+    /// a client connected to the specified slot
+    ClientConnect(u32),
+    /// the userinfo of the client in a slot changed, such as its name or team
+    ClientUserinfoChanged {
+        slot: u32,
+        info: HashMap<String, String>
+    },
+    /// the client in a slot disconnected
+    ClientDisconnect(u32),
+    /// a player killed another player, or themselves when both slots match
+    Kill {
+        killer: u32,
+        victim: u32,
+        means: String
+    },
+    /// the weapon statistics of the client in a slot at the end of a round
+    WeaponStats {
+        slot: u32,
+        weapons: Vec<WeaponStat>,
+        damage_given: u32,
+        damage_received: u32,
+        team_damage: u32,
+        gibs: u32
+    },
+    /// a medic revived another player
+    Revive {
+        medic: u32,
+        revived: u32
+    },
+    /// a player completed an objective action, such as planting or defusing
+    /// dynamite
+    Objective {
+        slot: u32,
+        action: String
+    },
+    /// a player said something in global or team chat
+    Say {
+        name: String,
+        text: String
+    },
+    /// a round ended for the specified reason, with its winner if it had one
+    Exit {
+        reason: String,
+        winner: Option<Team>
+    },
+    /// the server shut the map down
+    ShutdownGame
+}
+
+/// parses a single line of an ET `games.log` or `etconsole.log`. the leading
+/// server time is optional. lines the bot does not care about, and lines that
+/// are malformed, are ignored.
+///
+/// # Example
+///
+/// ```
+/// use et_mm_bot::et::log:: {
+///     self,
+///     Event,
+///     Team
+/// };
+///
+/// let event = log::parse_line(" 12:34 Exit: Allies team wins");
+/// assert_eq!(event, Some(Event::Exit { reason: "Allies team wins".to_string(), winner: Some(Team::Allies) }));
+/// ```
+pub fn parse_line (line: &str) -> Option<Event> {
+    let line = strip_time(line.trim());
+    let (kind, rest) = match line.find(':') {
+        Some(i) => (&line[..i], line[i + 1..].trim()),
+        None => return None
+    };
+    match kind {
+        "InitGame" => Some(Event::InitGame(query::parse_infostring(rest))),
+        "ClientConnect" => rest.parse().ok().map(Event::ClientConnect),
+        "ClientDisconnect" => rest.parse().ok().map(Event::ClientDisconnect),
+        "ClientUserinfoChanged" => {
+            let mut tokens = rest.splitn(2, ' ');
+            let slot = tokens.next()?.parse().ok()?;
+            let info = query::parse_infostring(tokens.next().unwrap_or(""));
+            Some(Event::ClientUserinfoChanged { slot, info })
+        },
+        "Kill" => parse_kill(rest),
+        "WeaponStats" => parse_weapon_stats(rest),
+        "Medic_Revive" => {
+            let slots: Vec<u32> = rest.split_whitespace()
+                .map(|s| s.parse().ok())
+                .collect::<Option<_>>()?;
+            match slots.as_slice() {
+                [medic, revived] => Some(Event::Revive { medic: *medic, revived: *revived }),
+                _ => None
+            }
+        },
+        "Dynamite_Plant" | "Dynamite_Diffuse" => rest.parse().ok().map(|slot| Event::Objective {
+            slot,
+            action: kind.to_string()
+        }),
+        "say" | "sayteam" => {
+            let (name, text) = match rest.find(": ") {
+                Some(i) => (&rest[..i], &rest[i + 2..]),
+                None => return None
+            };
+            Some(Event::Say { name: name.to_string(), text: text.to_string() })
+        },
+        "Exit" => {
+            let winner = match rest.to_lowercase().as_ref() {
+                "axis team wins" | "axis team wins." => Some(Team::Axis),
+                "allies team wins" | "allies team wins." => Some(Team::Allies),
+                _ => None
+            };
+            Some(Event::Exit { reason: rest.to_string(), winner })
+        },
+        "ShutdownGame" => Some(Event::ShutdownGame),
+        _ => None
+    }
+}
+
+/// removes the `mm:ss` server time ET prefixes its log lines with.
+fn strip_time (line: &str) -> &str {
+    let mut tokens = line.splitn(2, ' ');
+    let first = tokens.next().unwrap_or("");
+    let is_time = first.contains(':')
+        && first.chars().all(|c| c.is_ascii_digit() || c == ':');
+    match (is_time, tokens.next()) {
+        (true, Some(rest)) => rest.trim_start(),
+        _ => line
+    }
+}
+
+/// parses the `<killer> <victim> <means>: <names> by <MOD>` part of a kill.
+fn parse_kill (rest: &str) -> Option<Event> {
+    let (numbers, description) = match rest.find(':') {
+        Some(i) => (&rest[..i], &rest[i + 1..]),
+        None => (rest, "")
+    };
+    let numbers: Vec<u32> = numbers.split_whitespace()
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+    match numbers.as_slice() {
+        [killer, victim, _] => Some(Event::Kill {
+            killer: *killer,
+            victim: *victim,
+            means: description.rsplit(" by ").next().unwrap_or("").trim().to_string()
+        }),
+        _ => None
+    }
+}
+
+/// parses the `<slot> <rounds> <weapon mask> [<hits> <shots> <kills> <deaths>
+/// <headshots>]... <damage given> <damage received> <team damage given>
+/// <team damage received> <gibs> ...` part of a WeaponStats line, with one
+/// group of weapon values per bit in the mask. older servers stop logging
+/// after the damage values, so the trailing values are optional.
+fn parse_weapon_stats (rest: &str) -> Option<Event> {
+    let numbers: Vec<u32> = rest.split_whitespace()
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+    if numbers.len() < 3 {
+        return None;
+    }
+    let (slot, mask) = (numbers[0], numbers[2]);
+    let mut values = numbers[3..].chunks(WEAPON_FIELDS);
+    let mut weapons = Vec::new();
+    for weapon in (0..32).filter(|w| mask & (1 << w) != 0) {
+        match values.next() {
+            Some([hits, shots, kills, deaths, headshots]) => weapons.push(WeaponStat {
+                weapon,
+                hits: *hits,
+                shots: *shots,
+                kills: *kills,
+                deaths: *deaths,
+                headshots: *headshots
+            }),
+            _ => return None
+        };
+    }
+    let damage = &numbers[3 + weapons.len() * WEAPON_FIELDS..];
+    Some(Event::WeaponStats {
+        slot,
+        weapons,
+        damage_given: damage.first().copied().unwrap_or(0),
+        damage_received: damage.get(1).copied().unwrap_or(0),
+        team_damage: damage.get(2).copied().unwrap_or(0),
+        gibs: damage.get(4).copied().unwrap_or(0)
+    })
+}
+
+/// Clients structure tracking the userinfo of every connected client, as
+/// read from the log of a server
+///
+/// # Members
+///
+/// ```text
+/// slots: latest userinfo of the client in each slot
+/// ```
+#[derive(Default)]
+pub struct Clients {
+    slots: HashMap<u32, HashMap<String, String>>
+}
+
+// Clients implementation
+impl Clients {
+    /// constructs an empty set of clients.
+    pub fn construct () -> Self {
+        Self::default()
+    }
+    /// updates the tracked clients from a log event.
+    pub fn update (&mut self, event: &Event) {
+        match event {
+            Event::ClientConnect(slot) => {
+                self.slots.insert(*slot, HashMap::new());
+            },
+            Event::ClientUserinfoChanged { slot, info } => {
+                self.slots.entry(*slot).or_default().extend(info.clone());
+            },
+            Event::ClientDisconnect(slot) => {
+                self.slots.remove(slot);
+            },
+            _ => ()
+        };
+    }
+    /// returns the userinfo of the client in the specified slot.
+    pub fn get (&self, slot: u32) -> Option<&HashMap<String, String>> {
+        self.slots.get(&slot)
+    }
+    /// returns whether no clients are connected.
+    pub fn is_empty (&self) -> bool {
+        self.slots.is_empty()
+    }
+    /// returns the slot and userinfo of the client with the specified name,
+    /// including colour codes, as it appears in chat lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use et_mm_bot::et::log:: {
+    ///     self,
+    ///     Clients
+    /// };
+    ///
+    /// let mut clients = Clients::construct();
+    /// clients.update(&log::parse_line("ClientUserinfoChanged: 3 n\\^1red\\t\\1").unwrap());
+    /// assert_eq!(clients.find_by_name("^1red").unwrap().0, 3);
+    /// ```
+    pub fn find_by_name (&self, name: &str) -> Option<(u32, &HashMap<String, String>)> {
+        self.slots.iter()
+            .find(|(_, info)| info.get("n").map(|n| n.as_str()) == Some(name))
+            .map(|(slot, info)| (*slot, info))
+    }
+}
+
+/// LogTail structure for following a log file as the server writes to it
+///
+/// # Members
+///
+/// ```text
+/// path: path of the log file
+/// position: offset up to which the file has been read
+/// partial: trailing text of a line the server has not finished writing
+/// ```
+pub struct LogTail {
+    path: PathBuf,
+    position: u64,
+    partial: String
+}
+
+// LogTail implementation
+impl LogTail {
+    /// constructs a tail that starts at the current end of the log file, so
+    /// lines of earlier matches are not read again. a missing file is read
+    /// from its start once it is created.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use et_mm_bot::et::log::LogTail;
+    ///
+    /// let mut tail = LogTail::construct("/home/et/.etwolf/etpro/etconsole.log");
+    /// for line in tail.read_lines().unwrap() {
+    ///     println!("{}", line);
+    /// }
+    /// ```
+    pub fn construct (path: &str) -> Self {
+        let position = std::fs::metadata(path)
+            .map(|m| m.len())
+            .unwrap_or(0);
+        Self {
+            path: PathBuf::from(path),
+            position,
+            partial: String::new()
+        }
+    }
+    /// returns every complete line written to the log file since the last
+    /// read. a file that shrank was rotated, so it is read from its start.
+    pub fn read_lines (&mut self) -> io::Result<Vec<String>> {
+        let mut file = match File::open(&self.path) {
+            Ok (f) => f,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok (Vec::new()),
+            Err(e) => return Err(e)
+        };
+        let length = file.metadata()?.len();
+        if length < self.position {
+            self.position = 0;
+            self.partial.clear();
+        }
+        file.seek(SeekFrom::Start(self.position))?;
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+        self.position += buffer.len() as u64;
+        self.partial.push_str(&String::from_utf8_lossy(&buffer));
+        let mut lines: Vec<String> = self.partial.split('\n')
+            .map(|l| l.trim_end_matches('\r').to_string())
+            .collect();
+        self.partial = lines.pop().unwrap_or_default();
+        Ok (lines)
+    }
+}
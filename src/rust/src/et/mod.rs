@@ -0,0 +1,4 @@
+pub mod log;
+pub mod oob;
+pub mod query;
+pub mod rcon;
@@ -0,0 +1,86 @@
+use std:: {
+    io,
+    net:: {
+        SocketAddr,
+        ToSocketAddrs,
+        UdpSocket
+    },
+    time:: {
+        Duration,
+        Instant
+    }
+};
+
+/// header that starts every out-of-band packet of the quake 3 protocol.
+pub const HEADER: &[u8] = b"\xff\xff\xff\xff";
+
+/// how long to wait for further packets of a response once the first packet
+/// has arrived. servers send multi-packet responses back to back.
+const GAP: Duration = Duration::from_millis(250);
+
+/// largest packet a quake 3 server sends.
+const MAX_PACKET: usize = 16384;
+
+/// resolves a server address such as `et.example.com:27960`.
+pub fn resolve (address: &str) -> io::Result<SocketAddr> {
+    match address.to_socket_addrs()?.next() {
+        Some(a) => Ok (a),
+        None => Err(io::Error::new(io::ErrorKind::NotFound, format!("could not resolve address: {}", address)))
+    }
+}
+
+/// sends an out-of-band request to a server and collects every packet of its
+/// response with the out-of-band header removed. the first packet must arrive
+/// within the timeout, after which packets are collected until the server
+/// stops sending. fails with `io::ErrorKind::TimedOut` if nothing arrives.
+///
+/// # Example
+///
+/// ```no_run
+/// use et_mm_bot::et::oob;
+/// use std::time::Duration;
+///
+/// let address = oob::resolve("127.0.0.1:27960").unwrap();
+/// let packets = oob::exchange(address, b"getstatus", Duration::from_secs(2)).unwrap();
+/// ```
+pub fn exchange (address: SocketAddr, request: &[u8], timeout: Duration) -> io::Result<Vec<Vec<u8>>> {
+    let local: SocketAddr = match address {
+        SocketAddr::V4(_) => "0.0.0.0:0".parse().unwrap(),
+        SocketAddr::V6(_) => "[::]:0".parse().unwrap()
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.connect(address)?;
+    let mut packet = HEADER.to_vec();
+    packet.extend_from_slice(request);
+    socket.send(&packet)?;
+
+    let mut packets = Vec::new();
+    let mut buffer = vec![0u8; MAX_PACKET];
+    let deadline = Instant::now() + timeout;
+    loop {
+        let wait = if packets.is_empty() {
+            match deadline.checked_duration_since(Instant::now()) {
+                Some(w) if w > Duration::from_millis(0) => w,
+                _ => return Err(io::Error::new(io::ErrorKind::TimedOut, "server did not respond"))
+            }
+        } else {
+            GAP
+        };
+        socket.set_read_timeout(Some(wait))?;
+        match socket.recv(&mut buffer) {
+            Ok (length) => {
+                // ignore anything that is not an out-of-band packet
+                if length >= HEADER.len() && &buffer[..HEADER.len()] == HEADER {
+                    packets.push(buffer[HEADER.len()..length].to_vec());
+                }
+            },
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut => {
+                if packets.is_empty() {
+                    return Err(io::Error::new(io::ErrorKind::TimedOut, "server did not respond"));
+                }
+                return Ok (packets);
+            },
+            Err(e) => return Err(e)
+        };
+    }
+}
@@ -0,0 +1,303 @@
+use crate::et::oob;
+use std:: {
+    collections::HashMap,
+    error,
+    fmt,
+    io,
+    time::Duration
+};
+
+/// QueryError enumeration for the ways a server query can fail
+#[derive(Debug)]
+pub enum QueryError {
+    /// the server did not respond within the timeout
+    Timeout,
+    /// the server responded with something that could not be parsed
+    InvalidResponse(String),
+    /// the request could not be sent or received
+    Io(io::Error)
+}
+
+// Display implementation for QueryError
+impl fmt::Display for QueryError {
+    fn fmt (&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::Timeout => write!(f, "server did not respond to query"),
+            QueryError::InvalidResponse(r) => write!(f, "invalid query response: {}", r),
+            QueryError::Io(e) => write!(f, "query connection error: {}", e)
+        }
+    }
+}
+
+// Error implementation for QueryError
+impl error::Error for QueryError {
+    fn source (&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            QueryError::Io(e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+// conversion from io errors, treating read timeouts as server timeouts
+impl From<io::Error> for QueryError {
+    fn from (e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => QueryError::Timeout,
+            _ => QueryError::Io(e)
+        }
+    }
+}
+
+/// removes quake 3 colour codes such as `^1` from a string, the way ET
+/// renders it. a `^` followed by any character but another `^` starts a
+/// colour code. a `^` followed by another `^` or ending the string is shown
+/// as it is, so `^^1` reads `^` once the `^1` colour code is removed.
+///
+/// # Example
+///
+/// ```
+/// use et_mm_bot::et::query;
+///
+/// assert_eq!(query::strip_colors("^1red^7white"), "redwhite");
+/// ```
+pub fn strip_colors (text: &str) -> String {
+    let mut clean = String::with_capacity(text.len());
+    let mut characters = text.chars().peekable();
+    while let Some(c) = characters.next() {
+        match (c, characters.peek()) {
+            ('^', Some(&n)) if n != '^' => {
+                characters.next();
+            },
+            _ => clean.push(c)
+        };
+    }
+    clean
+}
+
+/// parses a quake 3 infostring such as `\sv_hostname\my server\mapname\supply`
+/// into its keys and values.
+///
+/// # Example
+///
+/// ```
+/// use et_mm_bot::et::query;
+///
+/// let info = query::parse_infostring("\\mapname\\supply\\g_needpass\\1");
+/// assert_eq!(info["mapname"], "supply");
+/// ```
+pub fn parse_infostring (infostring: &str) -> HashMap<String, String> {
+    let mut info = HashMap::new();
+    let mut tokens = infostring.trim().trim_start_matches('\\').split('\\');
+    while let Some(key) = tokens.next() {
+        let value = tokens.next().unwrap_or("");
+        if !key.is_empty() {
+            info.insert(key.to_string(), value.to_string());
+        }
+    }
+    info
+}
+
+/// returns the name of an ET `g_gametype`.
+pub fn gametype_name (gametype: i32) -> &'static str {
+    match gametype {
+        2 => "objective",
+        3 => "stopwatch",
+        4 => "campaign",
+        5 => "last man standing",
+        6 => "map voting",
+        _ => "unknown"
+    }
+}
+
+/// Player structure for a player listed in a status response
+///
+/// # Members
+///
+/// ```text
+/// score: current score of the player
+/// ping: ping of the player, 999 while connecting
+/// name: name of the player including colour codes
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Player {
+    pub score: i32,
+    pub ping: i32,
+    pub name: String
+}
+
+// Player implementation
+impl Player {
+    /// parses a player line of a status response: `<score> <ping> "<name>"`.
+    pub fn parse (line: &str) -> Result<Self, QueryError> {
+        let invalid = || QueryError::InvalidResponse(format!("invalid player line: {}", line));
+        let mut fields = line.trim().splitn(3, ' ');
+        let score = fields.next().and_then(|s| s.parse().ok()).ok_or_else(invalid)?;
+        let ping = fields.next().and_then(|s| s.parse().ok()).ok_or_else(invalid)?;
+        let name = fields.next().ok_or_else(invalid)?.trim();
+        let name = name.strip_prefix('"').and_then(|n| n.strip_suffix('"')).unwrap_or(name);
+        Ok (
+            Self {
+                score,
+                ping,
+                name: name.to_string()
+            }
+        )
+    }
+    /// returns the name of the player without colour codes.
+    pub fn clean_name (&self) -> String {
+        strip_colors(&self.name)
+    }
+}
+
+/// ServerStatus structure for the response to a `getstatus` query
+///
+/// # Members
+///
+/// ```text
+/// hostname: sv_hostname of the server including colour codes
+/// map: mapname currently being played
+/// gametype: g_gametype of the server
+/// max_clients: sv_maxclients of the server
+/// needs_password: whether g_needpass is set
+/// gamename: gamename of the running mod, such as etmain or etpro
+/// info: every key of the server infostring
+/// players: players currently connected
+/// ```
+#[derive(Clone, Debug)]
+pub struct ServerStatus {
+    pub hostname: String,
+    pub map: String,
+    pub gametype: i32,
+    pub max_clients: i32,
+    pub needs_password: bool,
+    pub gamename: String,
+    pub info: HashMap<String, String>,
+    pub players: Vec<Player>
+}
+
+// ServerStatus implementation
+impl ServerStatus {
+    /// parses a `statusResponse` packet with the out-of-band header removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use et_mm_bot::et::query::ServerStatus;
+    ///
+    /// let response = "statusResponse\n\\sv_hostname\\^1pug\\mapname\\radar\n5 40 \"^2player\"\n";
+    /// let status = ServerStatus::parse(response).unwrap();
+    /// assert_eq!(status.players[0].clean_name(), "player");
+    /// ```
+    pub fn parse (response: &str) -> Result<Self, QueryError> {
+        let mut lines = response.lines();
+        match lines.next() {
+            Some(l) if l.trim() == "statusResponse" => (),
+            _ => return Err(QueryError::InvalidResponse(response.to_string()))
+        };
+        let info = parse_infostring(lines.next().unwrap_or(""));
+        let mut players = Vec::new();
+        for line in lines.filter(|l| !l.trim().is_empty()) {
+            players.push(Player::parse(line)?);
+        }
+        Ok (
+            Self {
+                hostname: value(&info, "sv_hostname"),
+                map: value(&info, "mapname"),
+                gametype: number(&info, "g_gametype"),
+                max_clients: number(&info, "sv_maxclients"),
+                needs_password: number(&info, "g_needpass") != 0,
+                gamename: value(&info, "gamename"),
+                info,
+                players
+            }
+        )
+    }
+    /// returns whether or not nobody is connected to the server.
+    pub fn is_empty (&self) -> bool {
+        self.players.is_empty()
+    }
+}
+
+/// ServerInfo structure for the response to a `getinfo` query
+///
+/// # Members
+///
+/// ```text
+/// hostname: hostname of the server including colour codes
+/// map: mapname currently being played
+/// gametype: gametype of the server
+/// clients: number of connected players
+/// max_clients: sv_maxclients of the server
+/// needs_password: whether the server requires a password
+/// gamename: gamename of the running mod, such as etmain or etpro
+/// info: every key of the infostring
+/// ```
+#[derive(Clone, Debug)]
+pub struct ServerInfo {
+    pub hostname: String,
+    pub map: String,
+    pub gametype: i32,
+    pub clients: i32,
+    pub max_clients: i32,
+    pub needs_password: bool,
+    pub gamename: String,
+    pub info: HashMap<String, String>
+}
+
+// ServerInfo implementation
+impl ServerInfo {
+    /// parses an `infoResponse` packet with the out-of-band header removed.
+    pub fn parse (response: &str) -> Result<Self, QueryError> {
+        let mut lines = response.lines();
+        match lines.next() {
+            Some(l) if l.trim() == "infoResponse" => (),
+            _ => return Err(QueryError::InvalidResponse(response.to_string()))
+        };
+        let info = parse_infostring(lines.next().unwrap_or(""));
+        Ok (
+            Self {
+                hostname: value(&info, "hostname"),
+                map: value(&info, "mapname"),
+                gametype: number(&info, "gametype"),
+                clients: number(&info, "clients"),
+                max_clients: number(&info, "sv_maxclients"),
+                needs_password: number(&info, "needpass") != 0,
+                gamename: value(&info, "gamename"),
+                info
+            }
+        )
+    }
+}
+
+/// returns the value of an infostring key, or an empty string if it is missing.
+fn value (info: &HashMap<String, String>, key: &str) -> String {
+    info.get(key).cloned().unwrap_or_default()
+}
+
+/// returns the numeric value of an infostring key, or 0 if it is missing.
+fn number (info: &HashMap<String, String>, key: &str) -> i32 {
+    info.get(key).and_then(|v| v.trim().parse().ok()).unwrap_or(0)
+}
+
+/// sends a `getstatus` query to the server at the specified address.
+///
+/// # Example
+///
+/// ```no_run
+/// use et_mm_bot::et::query;
+/// use std::time::Duration;
+///
+/// let status = query::status("127.0.0.1:27960", Duration::from_secs(2)).unwrap();
+/// println!("{} players on {}", status.players.len(), status.map);
+/// ```
+pub fn status (address: &str, timeout: Duration) -> Result<ServerStatus, QueryError> {
+    let packets = oob::exchange(oob::resolve(address)?, b"getstatus", timeout)?;
+    ServerStatus::parse(&String::from_utf8_lossy(&packets.concat()))
+}
+
+/// sends a `getinfo` query to the server at the specified address.
+pub fn info (address: &str, timeout: Duration) -> Result<ServerInfo, QueryError> {
+    let packets = oob::exchange(oob::resolve(address)?, b"getinfo etmm", timeout)?;
+    ServerInfo::parse(&String::from_utf8_lossy(&packets.concat()))
+}
@@ -0,0 +1,119 @@
+use crate::et::oob;
+use std:: {
+    error,
+    fmt,
+    io,
+    net::SocketAddr,
+    time::Duration
+};
+
+/// command the server prefixes every printed response with.
+const PRINT: &str = "print\n";
+
+/// RconError enumeration for the ways an rcon command can fail
+#[derive(Debug)]
+pub enum RconError {
+    /// the server rejected the rcon password
+    BadPassword,
+    /// the server does not have an rcon password set
+    NoPassword,
+    /// the server did not respond within the timeout
+    Timeout,
+    /// the server responded with something other than a printed response
+    InvalidResponse(String),
+    /// the request could not be sent or received
+    Io(io::Error)
+}
+
+// Display implementation for RconError
+impl fmt::Display for RconError {
+    fn fmt (&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RconError::BadPassword => write!(f, "bad rcon password"),
+            RconError::NoPassword => write!(f, "no rcon password set on the server"),
+            RconError::Timeout => write!(f, "server did not respond to rcon command"),
+            RconError::InvalidResponse(r) => write!(f, "invalid rcon response: {}", r),
+            RconError::Io(e) => write!(f, "rcon connection error: {}", e)
+        }
+    }
+}
+
+// Error implementation for RconError
+impl error::Error for RconError {
+    fn source (&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            RconError::Io(e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+// conversion from io errors, treating read timeouts as server timeouts
+impl From<io::Error> for RconError {
+    fn from (e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => RconError::Timeout,
+            _ => RconError::Io(e)
+        }
+    }
+}
+
+/// Rcon structure for sending remote console commands to an ET server
+///
+/// # Members
+///
+/// ```text
+/// address: address of the server
+/// password: rcon password of the server
+/// timeout: how long to wait for the server to respond
+/// ```
+pub struct Rcon {
+    address: SocketAddr,
+    password: String,
+    timeout: Duration
+}
+
+// Rcon implementation
+impl Rcon {
+    /// constructs an rcon client for the server at the specified address.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use et_mm_bot::et::rcon::Rcon;
+    /// use std::time::Duration;
+    ///
+    /// let rcon = Rcon::construct("127.0.0.1:27960", "password", Duration::from_secs(2)).unwrap();
+    /// let status = rcon.send("status").unwrap();
+    /// ```
+    pub fn construct (address: &str, password: &str, timeout: Duration) -> Result<Self, RconError> {
+        Ok (
+            Self {
+                address: oob::resolve(address)?,
+                password: password.to_string(),
+                timeout
+            }
+        )
+    }
+    /// sends an rcon command to the server and returns its reassembled
+    /// printed response.
+    pub fn send (&self, command: &str) -> Result<String, RconError> {
+        let request = format!("rcon {} {}", self.password, command);
+        let packets = oob::exchange(self.address, request.as_bytes(), self.timeout)?;
+        let mut response = String::new();
+        for packet in packets.iter() {
+            let packet = String::from_utf8_lossy(packet);
+            if !packet.starts_with(PRINT) {
+                return Err(RconError::InvalidResponse(packet.to_string()));
+            }
+            response.push_str(&packet[PRINT.len()..]);
+        }
+        if response.starts_with("Bad rconpassword") {
+            return Err(RconError::BadPassword);
+        }
+        if response.starts_with("No rconpassword set") {
+            return Err(RconError::NoPassword);
+        }
+        Ok (response)
+    }
+}
@@ -0,0 +1,2 @@
+// game server protocol support shared by the bot and its integration tests
+pub mod et;
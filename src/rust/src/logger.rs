@@ -1,44 +1,66 @@
 extern crate slog_async;
+extern crate slog_json;
 extern crate slog_term;
 
 use slog:: {
     Drain,
-    Logger
+    Level,
+    Logger,
+    Never
 };
 use self::slog_async:: {
    Async,
    AsyncGuard
 };
+use self::slog_json::Json;
 use self::slog_term:: {
+    CompactFormat,
     FullFormat,
     TermDecorator
 };
+use std::io;
+
+/// Format enumeration for how log records are written
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// one line per record with every key-value pair
+    Full,
+    /// records grouped under their shared key-value pairs
+    Compact,
+    /// one json object per record, for log collectors
+    Json
+}
 
 // Log structure containing a logger and asynchronous guard
 pub struct Log {
+    // held only to keep the async drain alive until the log is dropped
+    #[allow(dead_code)]
     pub guard:  AsyncGuard,
     pub logger: Logger
 }
 
 // Log implementation
 impl Log {
-    /// constructs a new Log instance with a logger and asynchronous guard.
-    /// care should be taken to drop the logger (and therefore the guard)
-    /// before exiting / panicing to avoid mangling debug messages.
+    /// constructs a new Log instance with a logger and asynchronous guard,
+    /// writing records of at least the specified level in the specified
+    /// format. care should be taken to drop the logger (and therefore the
+    /// guard) before exiting / panicing to avoid mangling debug messages.
     ///
     /// # Example
     ///
     /// ```
-    /// let log = logger::Log::new()
+    /// let log = logger::Log::new(Level::Debug, logger::Format::Full)
     /// debug!(log.logger, "debug message")
     /// drop(log)
     /// panic!("panic message")
     /// ```
-    pub fn new() -> Self {
-        let decor = TermDecorator::new().build();
-        let drain = FullFormat::new(decor)
-            .build()
-            .fuse();
+    pub fn new(level: Level, format: Format) -> Self {
+        let drain: Box<dyn Drain<Ok = (), Err = Never> + Send> = match format {
+            Format::Full => Box::new(FullFormat::new(TermDecorator::new().build()).build().fuse()),
+            Format::Compact => Box::new(CompactFormat::new(TermDecorator::new().build()).build().fuse()),
+            Format::Json => Box::new(Json::default(io::stderr()).fuse())
+        };
+        let drain = drain.filter_level(level).fuse();
         let drain = Async::new(drain).build_with_guard();
         let guard = drain.1;
         let drain = drain.0.fuse();
@@ -4,50 +4,193 @@ extern crate serenity;
 extern crate slog;
 
 mod bot;
+mod cli;
 mod config;
 mod database;
 #[macro_use]
 mod logger;
+mod match_making;
 
-use std::sync::Arc;
+use clap::Parser;
+use std:: {
+    error::Error,
+    path::Path,
+    sync::Arc,
+    time::Duration
+};
 
 fn main() {
-    // initialize logger
-    let log = Arc::new(logger::Log::new());
+    // process arguments
+    let cli = cli::Cli::parse();
 
-    // FIXME: eventually this will be where arguements are processed
-    //        for now just hardcode these parameters
-    let bot_config_path: &'static str = "/opt/et-mm-bot/config.cfg";
+    // initialize logger
+    let log = Arc::new(logger::Log::new(cli.log_level.level(), cli.log_format));
 
     // initialize bot
 	info!(log.logger, "ET-MM Bot version {}", env!("CARGO_PKG_VERSION"));
 
+    // convert a configuration file from before the toml configuration, once
+    let legacy_config_path = Path::new(&cli.config).with_extension("cfg");
+    let legacy_config_path = legacy_config_path.to_string_lossy();
+    if !Path::new(&cli.config).exists() && Path::new(legacy_config_path.as_ref()).exists() {
+        if cli.dry_run {
+            error!(log.logger, "\tconfiguration file needs converting to toml, run without --dry-run"; "file" => legacy_config_path.as_ref());
+            drop(log);
+            panic!();
+        }
+        info!(log.logger, "converting configuration file to toml..."; "file" => legacy_config_path.as_ref());
+        if let Err(e) = config::convert(&legacy_config_path, &cli.config) {
+            error!(log.logger, "\t{}", e; "file" => legacy_config_path.as_ref());
+            drop(log);
+            panic!();
+        }
+    }
+
     //load bot configuration
     info!(log.logger, "loading configuration into memory...");
-    let config = match config::Config::construct(bot_config_path) {
+    let config = match config::Config::construct(&cli.config) {
         Ok (b) => b,
         Err(e) => {
-            error!(log.logger, "\t{}", e; "file" => bot_config_path);
+            error!(log.logger, "\t{}", e; "file" => &cli.config);
             drop(log);
             panic!();
         }
     };
-    
-    // initialize database object
+    for variable in config.overrides.iter() {
+        info!(log.logger, "\tconfiguration key overridden by environment"; "variable" => variable);
+    }
+
+    // run the requested command
+    let dry_run = cli.dry_run;
+    let config_path = &cli.config;
+    let result = match cli.command.unwrap_or(cli::Command::Run) {
+        cli::Command::Run => {
+            run(log, config, dry_run);
+            return;
+        },
+        cli::Command::CheckConfig => {
+            check_config(&log, &config);
+            Ok (())
+        },
+        cli::Command::Migrate => connect(&log, &config)
+            .and_then(|d| migrate(&log, &config, &d, dry_run)),
+        cli::Command::AddGroup { name, capacity, mode, maps, subscription_ttl, channels } => {
+            let group = match_making::draft::TeamSelection::parse(&mode).map(|mode| config::GroupSettings {
+                name: name.to_lowercase(),
+                capacity,
+                mode,
+                maps: maps.iter().map(|m| m.to_lowercase()).collect(),
+                subscription_ttl,
+                channels
+            });
+            group.and_then(|g| add_group(&log, config_path, &config, &g, dry_run))
+        }
+    };
+    if let Err(e) = result {
+        error!(log.logger, "\t{}", e);
+        drop(log);
+        panic!();
+    }
+}
+
+/// connects to the database configured in the bot configuration.
+fn connect (log: &Arc<logger::Log>, config: &config::Config) -> Result<Arc<database::Database>, Box<dyn Error>> {
     info!(log.logger, "initializing database object...");
-    let database = match database::Database::construct(&config.database_connection_string, &log) {
-        Ok (d) => Arc::new(d),
+    match database::Database::construct(&config.database, log) {
+        Ok (d) => Ok (Arc::new(d)),
+        Err(e) => Err(format!("{} (connecting to: {})", e, config.database.address()).into())
+    }
+}
+
+/// brings the database schema up to date and adds the configured match
+/// making groups and game servers to the database. a dry run checks the
+/// pending migrations without applying them and only lists the groups and
+/// servers.
+fn migrate (log: &Arc<logger::Log>, config: &config::Config, database: &database::Database, dry_run: bool) -> Result<(), Box<dyn Error>> {
+    // apply pending schema migrations
+    info!(log.logger, "migrating database schema...");
+    database.migrate(dry_run)?;
+
+    let server_names: Vec<String> = config.servers.iter()
+        .map(|s| s.name.clone())
+        .collect();
+    if dry_run {
+        info!(log.logger, "dry run: not adding configured match making groups and game servers";
+            "groups" => config.group_names().join(" "), "servers" => server_names.join(" "));
+        return Ok (());
+    }
+
+    // add match making groups to database
+    info!(log.logger, "adding configured match making groups...");
+    database.add_mm_groups(&config.group_names())?;
+
+    // add game servers to database
+    info!(log.logger, "adding configured game servers...");
+    database.add_servers(&server_names)?;
+    Ok (())
+}
+
+/// logs a summary of the verified bot configuration. the values of keys
+/// overridden by the environment are left out since they are often secrets.
+fn check_config (log: &Arc<logger::Log>, config: &config::Config) {
+    info!(log.logger, "configuration is valid";
+        "database" => config.database.address(), "sslmode" => config.database.sslmode.unwrap_or_default().as_str(),
+        "groups" => config.groups.len(), "servers" => config.servers.len(), "admin roles" => config.admins.len(), "overrides" => config.overrides.len());
+    for group in config.groups.iter() {
+        let capacity = match group.capacity {
+            Some(c) => c,
+            None => match_making::queue::capacity(&group.name).unwrap_or_default()
+        };
+        info!(log.logger, "\tmatch making group"; "group" => &group.name, "capacity" => capacity,
+            "mode" => group.mode.as_str(), "maps" => group.maps.len(), "channels" => group.channels.len());
+    }
+    for server in config.servers.iter() {
+        info!(log.logger, "\tgame server"; "server" => &server.name, "address" => server.address(), "region" => &server.region);
+    }
+}
+
+/// adds a match making group to the configuration file and the database. a
+/// dry run only shows the table that would be added to the configuration
+/// file.
+fn add_group (log: &Arc<logger::Log>, path: &str, config: &config::Config, group: &config::GroupSettings, dry_run: bool) -> Result<(), Box<dyn Error>> {
+    let table = config::append_group(path, group, dry_run)?;
+    if dry_run {
+        info!(log.logger, "dry run: not adding match making group"; "group" => &group.name, "table" => table);
+        return Ok (());
+    }
+    info!(log.logger, "added match making group to configuration file"; "group" => &group.name, "file" => path);
+    connect(log, config)?.add_mm_groups(std::slice::from_ref(&group.name))
+}
+
+/// runs the bot until it is shut down. a dry run checks the pending
+/// migrations and stops before restoring the match making state, since the
+/// stored functions it uses are rolled back with the migrations.
+fn run (log: Arc<logger::Log>, config: config::Config, dry_run: bool) {
+    // initialize database object
+    let database = match connect(&log, &config) {
+        Ok (d) => d,
         Err(e) => {
-            error!(log.logger, "\t{}", e; "connection string" => config.database_connection_string);
+            error!(log.logger, "\t{}", e);
             drop(log);
             panic!();
         }
     };
 
-    // add match making groups to database
-    info!(log.logger, "adding configured match making groups...");
-    match database.add_mm_groups(&config.mm_groups) {
-        Ok (_) => (),
+    // migrate database schema and add match making groups and game servers
+    if let Err(e) = migrate(&log, &config, &database, dry_run) {
+        error!(log.logger, "\t{}", e);
+        drop(log);
+        panic!();
+    }
+    if dry_run {
+        info!(log.logger, "dry run: not restoring match making state or connecting to discord");
+        return;
+    }
+
+    // initialize match making queues
+    info!(log.logger, "initializing match making queues...");
+    let mut queues = match match_making::queue::Queues::construct(&config.group_names(), &config.capacities()) {
+        Ok (q) => q,
         Err(e) => {
             error!(log.logger, "\t{}", e);
             drop(log);
@@ -55,9 +198,93 @@ fn main() {
         }
     };
 
+    // restore subscribed users to match making queues
+    info!(log.logger, "restoring match making queue subscriptions...");
+    for group in config.group_names().iter() {
+        let users = match database.get_mm_users(group) {
+            Ok (u) => u,
+            Err(e) => {
+                error!(log.logger, "\t{}", e; "group" => group);
+                drop(log);
+                panic!();
+            }
+        };
+        for user in users.iter() {
+            if let Err(e) = queues.restore(group, *user) {
+                error!(log.logger, "\t{}", e; "group" => group);
+                drop(log);
+                panic!();
+            }
+        }
+    }
+
+    // initialize ready checks
+    let mut checks = match_making::ready_check::ReadyChecks::construct(Duration::from_secs(config.match_making.ready_timeout));
+
+    // initialize drafts
+    let mut drafts = match_making::draft::Drafts::construct(config.selections(), Duration::from_secs(config.match_making.pick_timeout));
+
+    // initialize map votes
+    let mut votes = match_making::map_vote::MapVotes::construct(config.map_pools(), config.match_making.recent_maps, Duration::from_secs(config.match_making.vote_timeout));
+
+    // initialize active matches
+    let mut matches = match_making::matches::Matches::construct();
+
+    // restore the match lifecycle from before the last shutdown
+    info!(log.logger, "restoring ready checks, map votes, drafts and matches...");
+    match database.get_ready_checks() {
+        Ok (restored) => restored.into_iter().for_each(|check| checks.add(check)),
+        Err(e) => {
+            error!(log.logger, "\t{}", e);
+            drop(log);
+            panic!();
+        }
+    };
+    match database.get_map_votes() {
+        Ok (restored) => restored.into_iter().for_each(|vote| votes.add(vote)),
+        Err(e) => {
+            error!(log.logger, "\t{}", e);
+            drop(log);
+            panic!();
+        }
+    };
+    match database.get_drafts(drafts.timeout) {
+        Ok (restored) => restored.into_iter().for_each(|draft| drafts.add(draft)),
+        Err(e) => {
+            error!(log.logger, "\t{}", e);
+            drop(log);
+            panic!();
+        }
+    };
+    match database.get_active_matches() {
+        Ok (restored) => restored.into_iter().for_each(|game| matches.add(game)),
+        Err(e) => {
+            error!(log.logger, "\t{}", e);
+            drop(log);
+            panic!();
+        }
+    };
+
+    // initialize subscription expiries, converting times to live from minutes
+    let ttls = config.subscription_ttls().into_iter()
+        .map(|(g, t)| (g, Duration::from_secs(t * 60)))
+        .collect();
+    let expiries = match_making::expiry::Expiries::construct(
+        Duration::from_secs(config.match_making.subscription_ttl * 60),
+        ttls,
+        Duration::from_secs(config.match_making.expiry_grace)
+    );
+
+    // initialize game server pool
+    let servers = match_making::servers::Servers::construct(config.servers.clone());
+
     // initialize bot
     info!(log.logger, "initializing discord bot...");
-    let mut bot = match bot::Bot::construct(&config.discord_token, &database, &log) {
+    let leaderboards = bot::leaderboards::Leaderboards::construct(config.match_making.provisional_games);
+    let admins = bot::admins::Admins::construct(&config.admin_roles());
+    let channels = bot::channels::Channels::construct(config.channel_groups());
+    let state = bot::State { queues, checks, drafts, votes, matches, expiries, servers, leaderboards, admins, channels };
+    let mut bot = match bot::Bot::construct(&config.discord.token, &database, state, &log) {
         Ok (b) => b,
         Err(e) => {
             error!(log.logger, "\t{}", e);
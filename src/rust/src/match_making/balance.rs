@@ -0,0 +1,213 @@
+use crate::match_making::rating;
+
+/// largest number of players that are balanced by trying every possible split.
+/// larger matches fall back to a snake draft ordered by rating.
+const EXHAUSTIVE_LIMIT: usize = 20;
+
+/// RatedPlayer structure pairing a player with their rating
+///
+/// # Members
+///
+///     ```
+///     discord_uuid: discord unique user id of the player
+///     rating: rating of the player within the match making group
+///     ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RatedPlayer {
+    pub discord_uuid: u64,
+    pub rating: f64
+}
+
+/// Teams structure for a balanced match
+///
+/// # Members
+///
+///     ```
+///     axis: players on the axis team
+///     allies: players on the allies team
+///     ```
+#[derive(Debug)]
+pub struct Teams {
+    pub axis: Vec<RatedPlayer>,
+    pub allies: Vec<RatedPlayer>
+}
+
+// Teams implementation
+impl Teams {
+    /// returns the average rating of the axis team.
+    pub fn axis_rating (&self) -> f64 {
+        average(&self.axis)
+    }
+    /// returns the average rating of the allies team.
+    pub fn allies_rating (&self) -> f64 {
+        average(&self.allies)
+    }
+    /// returns the probability of the axis team winning based on average ratings.
+    pub fn axis_win_chance (&self) -> f64 {
+        rating::expected_score(self.axis_rating(), self.allies_rating())
+    }
+}
+
+/// returns the average rating of a group of players.
+pub fn average (players: &[RatedPlayer]) -> f64 {
+    if players.is_empty() {
+        return 0.0;
+    }
+    total(players) / players.len() as f64
+}
+
+/// returns the summed rating of a group of players.
+fn total (players: &[RatedPlayer]) -> f64 {
+    players.iter().map(|p| p.rating).sum()
+}
+
+/// splits players into two teams whose summed ratings differ as little as
+/// possible. when the number of players is odd the allies team receives the
+/// extra player. ties are broken in favour of the first split found, so the
+/// result only depends on the order of the players passed in.
+///
+/// # Example
+///
+/// ```
+/// let teams = balance::balance(&players);
+/// println!("axis: {:.0} allies: {:.0}", teams.axis_rating(), teams.allies_rating());
+/// ```
+pub fn balance (players: &[RatedPlayer]) -> Teams {
+    if players.len() > EXHAUSTIVE_LIMIT {
+        return snake_draft(players);
+    }
+    let axis_size = players.len() / 2;
+    let total = total(players);
+    let mut best_mask: u32 = 0;
+    let mut best_difference = f64::INFINITY;
+    for mask in 0u32..(1 << players.len()) {
+        if mask.count_ones() as usize != axis_size {
+            continue;
+        }
+        let axis: f64 = players.iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, p)| p.rating)
+            .sum();
+        let difference = (total - 2.0 * axis).abs();
+        if difference < best_difference {
+            best_difference = difference;
+            best_mask = mask;
+        }
+    }
+    let mut teams = Teams {
+        axis: Vec::new(),
+        allies: Vec::new()
+    };
+    for (i, player) in players.iter().enumerate() {
+        if best_mask & (1 << i) != 0 {
+            teams.axis.push(*player);
+        } else {
+            teams.allies.push(*player);
+        }
+    }
+    teams
+}
+
+/// splits players into two teams by sorting them by rating and alternating
+/// picks in an ABBA order.
+fn snake_draft (players: &[RatedPlayer]) -> Teams {
+    let mut sorted = players.to_vec();
+    sorted.sort_by(|a, b| b.rating.partial_cmp(&a.rating).unwrap_or(std::cmp::Ordering::Equal));
+    let mut teams = Teams {
+        axis: Vec::new(),
+        allies: Vec::new()
+    };
+    for (i, player) in sorted.into_iter().enumerate() {
+        if i % 4 == 0 || i % 4 == 3 {
+            teams.allies.push(player);
+        } else {
+            teams.axis.push(player);
+        }
+    }
+    teams
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players (ratings: &[f64]) -> Vec<RatedPlayer> {
+        ratings.iter()
+            .enumerate()
+            .map(|(i, r)| RatedPlayer { discord_uuid: i as u64, rating: *r })
+            .collect()
+    }
+
+    fn difference (teams: &Teams) -> f64 {
+        (total(&teams.axis) - total(&teams.allies)).abs()
+    }
+
+    #[test]
+    fn one_versus_one() {
+        let teams = balance(&players(&[1200.0, 900.0]));
+        assert_eq!(teams.axis.len(), 1);
+        assert_eq!(teams.allies.len(), 1);
+    }
+
+    #[test]
+    fn three_versus_three_is_perfectly_balanced() {
+        // 1500 + 1000 + 900 = 1400 + 1100 + 900
+        let teams = balance(&players(&[1500.0, 1400.0, 1100.0, 1000.0, 900.0, 900.0]));
+        assert_eq!(teams.axis.len(), 3);
+        assert_eq!(teams.allies.len(), 3);
+        assert!(difference(&teams) < 1e-9);
+    }
+
+    #[test]
+    fn self_sorted_stack_is_split() {
+        // the three strongest players queued together must not end up together
+        let teams = balance(&players(&[1800.0, 1750.0, 1700.0, 900.0, 850.0, 800.0]));
+        let strong_on_axis = teams.axis.iter().filter(|p| p.rating > 1500.0).count();
+        assert!(strong_on_axis == 1 || strong_on_axis == 2);
+    }
+
+    #[test]
+    fn six_versus_six_is_optimal() {
+        let ratings = [1620.0, 1580.0, 1490.0, 1310.0, 1200.0, 1150.0, 1100.0, 1040.0, 990.0, 930.0, 870.0, 800.0];
+        let teams = balance(&players(&ratings));
+        assert_eq!(teams.axis.len(), 6);
+        assert_eq!(teams.allies.len(), 6);
+        // summed ratings are even so a difference below 20 is only possible if
+        // the search found one of the best splits
+        assert!(difference(&teams) <= 20.0);
+    }
+
+    #[test]
+    fn every_player_is_placed_once() {
+        let teams = balance(&players(&[1000.0; 12]));
+        let mut placed: Vec<u64> = teams.axis.iter()
+            .chain(teams.allies.iter())
+            .map(|p| p.discord_uuid)
+            .collect();
+        placed.sort();
+        assert_eq!(placed, (0..12).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn odd_player_goes_to_allies() {
+        let teams = balance(&players(&[1000.0, 1000.0, 1000.0]));
+        assert_eq!(teams.axis.len(), 1);
+        assert_eq!(teams.allies.len(), 2);
+    }
+
+    #[test]
+    fn large_matches_use_snake_draft() {
+        let ratings: Vec<f64> = (0..24).map(|i| 1000.0 + i as f64 * 10.0).collect();
+        let teams = balance(&players(&ratings));
+        assert_eq!(teams.axis.len(), 12);
+        assert_eq!(teams.allies.len(), 12);
+        assert!(difference(&teams) <= 120.0);
+    }
+
+    #[test]
+    fn equal_teams_have_even_win_chance() {
+        let teams = balance(&players(&[1300.0, 1100.0, 1100.0, 1300.0]));
+        assert!((teams.axis_win_chance() - 0.5).abs() < 1e-9);
+    }
+}
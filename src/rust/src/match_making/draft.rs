@@ -0,0 +1,450 @@
+use crate::match_making:: {
+    balance:: {
+        RatedPlayer,
+        Teams
+    },
+    matches::Team
+};
+use rand::seq::SliceRandom;
+use serde:: {
+    Deserialize,
+    Serialize
+};
+use std:: {
+    collections::HashMap,
+    convert::TryFrom,
+    error::Error,
+    time:: {
+        Duration,
+        SystemTime
+    }
+};
+
+/// CaptainSelection enumeration for how draft captains are chosen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaptainSelection {
+    Highest,
+    Random
+}
+
+/// TeamSelection enumeration for how a match making group forms its teams.
+/// it is written in the configuration file the same way it is parsed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TeamSelection {
+    #[default]
+    Balance,
+    Draft(CaptainSelection)
+}
+
+// TeamSelection implementation
+impl TeamSelection {
+    /// parses the team selection option of a match making group from the
+    /// configuration file: `balance`, `draft` (the highest-rated players are
+    /// captains) or `draft random`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let selection = draft::TeamSelection::parse("draft random").unwrap();
+    /// ```
+    pub fn parse (option: &str) -> Result<Self, Box<dyn Error>> {
+        let tokens: Vec<&str> = option.split_whitespace().collect();
+        match tokens.as_slice() {
+            [] | ["balance"] => Ok (TeamSelection::Balance),
+            ["draft"] | ["draft", "highest"] => Ok (TeamSelection::Draft(CaptainSelection::Highest)),
+            ["draft", "random"] => Ok (TeamSelection::Draft(CaptainSelection::Random)),
+            _ => Err(format!("unknown team selection: {}", option).into())
+        }
+    }
+    /// returns the configuration file representation of the team selection.
+    pub fn as_str (self) -> &'static str {
+        match self {
+            TeamSelection::Balance => "balance",
+            TeamSelection::Draft(CaptainSelection::Highest) => "draft",
+            TeamSelection::Draft(CaptainSelection::Random) => "draft random"
+        }
+    }
+}
+
+// TryFrom implementation for TeamSelection, used to deserialize it
+impl TryFrom<String> for TeamSelection {
+    type Error = String;
+    fn try_from (option: String) -> Result<Self, Self::Error> {
+        Self::parse(&option).map_err(|e| e.to_string())
+    }
+}
+
+// From implementation for String, used to serialize a TeamSelection
+impl From<TeamSelection> for String {
+    fn from (selection: TeamSelection) -> Self {
+        selection.as_str().to_string()
+    }
+}
+
+/// chooses two captains from the players of a match. the captain with the
+/// lower rating is returned first, since they pick first.
+pub fn choose_captains (players: &[RatedPlayer], selection: CaptainSelection) -> (RatedPlayer, RatedPlayer) {
+    let mut candidates = players.to_vec();
+    match selection {
+        CaptainSelection::Highest => candidates.sort_by(|a, b| b.rating.partial_cmp(&a.rating).unwrap_or(std::cmp::Ordering::Equal)),
+        CaptainSelection::Random => candidates.shuffle(&mut rand::thread_rng())
+    };
+    if candidates[0].rating <= candidates[1].rating {
+        (candidates[0], candidates[1])
+    } else {
+        (candidates[1], candidates[0])
+    }
+}
+
+/// Draft structure for captains picking their teams
+///
+/// # Members
+///
+///     ```
+///     id: database id of the draft, once saved
+///     group: match making group the players were popped from
+///     channel: discord channel id the draft is running in
+///     map: map chosen for the match, if the group votes on maps
+///     teams: players picked so far, with the first captain on axis
+///     pool: players that have not been picked yet
+///     picks: number of picks made so far
+///     timeout: how long a captain has for each pick
+///     deadline: time at which the current pick is made automatically
+///     ```
+pub struct Draft {
+    pub id: Option<i32>,
+    pub group: String,
+    pub channel: u64,
+    pub map: Option<String>,
+    pub teams: Teams,
+    pool: Vec<RatedPlayer>,
+    picks: usize,
+    timeout: Duration,
+    pub deadline: SystemTime
+}
+
+// Draft implementation
+impl Draft {
+    /// constructs a draft between two captains. the first captain leads axis
+    /// and picks first, after which picks alternate in an ABBA order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let captains = draft::choose_captains(&players, CaptainSelection::Highest);
+    /// let draft = draft::Draft::construct("6v6", channel, &players, captains, Duration::from_secs(30));
+    /// ```
+    pub fn construct (group: &str, channel: u64, players: &[RatedPlayer], captains: (RatedPlayer, RatedPlayer), timeout: Duration) -> Self {
+        let pool = players.iter()
+            .filter(|p| p.discord_uuid != captains.0.discord_uuid && p.discord_uuid != captains.1.discord_uuid)
+            .cloned()
+            .collect();
+        let mut draft = Self {
+            id: None,
+            group: group.to_string(),
+            channel,
+            map: None,
+            teams: Teams {
+                axis: vec![captains.0],
+                allies: vec![captains.1]
+            },
+            pool,
+            picks: 0,
+            timeout,
+            deadline: SystemTime::now() + timeout
+        };
+        draft.pick_last();
+        draft
+    }
+    /// rebuilds a draft in progress from the players picked for each team in
+    /// pick order, starting with their captains, and the players that have
+    /// not been picked yet. the picks are replayed so the draft continues
+    /// with the right captain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let draft = draft::Draft::restore("6v6", channel, &teams, &pool, Duration::from_secs(30)).unwrap();
+    /// ```
+    pub fn restore (group: &str, channel: u64, teams: &Teams, pool: &[RatedPlayer], timeout: Duration) -> Result<Self, Box<dyn Error>> {
+        if teams.axis.is_empty() || teams.allies.is_empty() {
+            return Err(format!("the `{}` draft has no captain for each team", group).into());
+        }
+        let players: Vec<RatedPlayer> = teams.axis.iter()
+            .chain(teams.allies.iter())
+            .chain(pool.iter())
+            .cloned()
+            .collect();
+        let mut draft = Self::construct(group, channel, &players, (teams.axis[0], teams.allies[0]), timeout);
+        while draft.teams.axis.len() < teams.axis.len() || draft.teams.allies.len() < teams.allies.len() {
+            let next = match draft.current_team() {
+                Team::Axis => teams.axis.get(draft.teams.axis.len()),
+                Team::Allies => teams.allies.get(draft.teams.allies.len())
+            };
+            let player = match next {
+                Some(p) => p.discord_uuid,
+                None => return Err(format!("the picks of the `{}` draft are out of order", group).into())
+            };
+            draft.pick(draft.current_captain(), player)?;
+        }
+        Ok (draft)
+    }
+    /// returns the players that have not been picked yet.
+    pub fn pool (&self) -> &[RatedPlayer] {
+        &self.pool
+    }
+    /// returns whether or not the specified player is part of the draft.
+    pub fn contains (&self, player: u64) -> bool {
+        self.pool.iter()
+            .chain(self.teams.axis.iter())
+            .chain(self.teams.allies.iter())
+            .any(|p| p.discord_uuid == player)
+    }
+    /// returns the team whose captain is currently picking.
+    pub fn current_team (&self) -> Team {
+        match self.picks % 4 {
+            0 | 3 => Team::Axis,
+            _ => Team::Allies
+        }
+    }
+    /// returns the captain that is currently picking.
+    pub fn current_captain (&self) -> u64 {
+        match self.current_team() {
+            Team::Axis => self.teams.axis[0].discord_uuid,
+            Team::Allies => self.teams.allies[0].discord_uuid
+        }
+    }
+    /// returns whether or not every player has been picked.
+    pub fn is_complete (&self) -> bool {
+        self.pool.is_empty()
+    }
+    /// returns whether or not the current pick has run out of time.
+    pub fn is_expired (&self, now: SystemTime) -> bool {
+        !self.is_complete() && now >= self.deadline
+    }
+    /// picks a player for the team of the specified captain. fails if it is not
+    /// their turn or the player is not available.
+    pub fn pick (&mut self, captain: u64, player: u64) -> Result<RatedPlayer, Box<dyn Error>> {
+        if self.is_complete() {
+            return Err("every player has already been picked.".into());
+        }
+        if captain != self.current_captain() {
+            return Err("it is not your turn to pick.".into());
+        }
+        let index = match self.pool.iter().position(|p| p.discord_uuid == player) {
+            Some(i) => i,
+            None => return Err("that player is not available to pick.".into())
+        };
+        Ok (self.take(index))
+    }
+    /// picks the highest rated available player for the captain whose pick
+    /// ran out of time.
+    pub fn auto_pick (&mut self) -> Option<RatedPlayer> {
+        let index = (0..self.pool.len())
+            .max_by(|a, b| self.pool[*a].rating.partial_cmp(&self.pool[*b].rating).unwrap_or(std::cmp::Ordering::Equal))?;
+        Some(self.take(index))
+    }
+    /// moves a player from the pool onto the picking team and starts the next pick.
+    fn take (&mut self, index: usize) -> RatedPlayer {
+        let player = self.pool.remove(index);
+        match self.current_team() {
+            Team::Axis => self.teams.axis.push(player),
+            Team::Allies => self.teams.allies.push(player)
+        };
+        self.picks += 1;
+        self.deadline = SystemTime::now() + self.timeout;
+        self.pick_last();
+        player
+    }
+    /// assigns the last player automatically since there is no choice left.
+    fn pick_last (&mut self) {
+        if self.pool.len() == 1 {
+            self.take(0);
+        }
+    }
+}
+
+/// Drafts structure holding every draft in progress
+///
+/// # Members
+///
+///     ```
+///     selections: team selection of every match making group using drafts
+///     timeout: how long a captain has for each pick
+///     drafts: drafts in progress
+///     ```
+pub struct Drafts {
+    selections: HashMap<String, TeamSelection>,
+    pub timeout: Duration,
+    drafts: Vec<Draft>
+}
+
+// Drafts implementation
+impl Drafts {
+    /// constructs an empty set of drafts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let drafts = draft::Drafts::construct(config.selections(), Duration::from_secs(config.match_making.pick_timeout));
+    /// ```
+    pub fn construct (selections: HashMap<String, TeamSelection>, timeout: Duration) -> Self {
+        Self {
+            selections,
+            timeout,
+            drafts: Vec::new()
+        }
+    }
+    /// returns how the specified match making group forms its teams.
+    pub fn selection (&self, group: &str) -> TeamSelection {
+        match self.selections.get(&group.to_lowercase()) {
+            Some(s) => *s,
+            None => TeamSelection::Balance
+        }
+    }
+    /// adds a draft.
+    pub fn add (&mut self, draft: Draft) {
+        self.drafts.push(draft);
+    }
+    /// returns every draft in progress.
+    pub fn all_mut (&mut self) -> &mut [Draft] {
+        &mut self.drafts
+    }
+    /// returns the draft the specified player is part of.
+    pub fn find (&mut self, player: u64) -> Option<&mut Draft> {
+        self.drafts.iter_mut().find(|d| d.contains(player))
+    }
+    /// makes an automatic pick in every draft whose current pick ran out of
+    /// time, returning the channel, captain and picked player of each.
+    pub fn auto_pick_expired (&mut self, now: SystemTime) -> Vec<(u64, u64, RatedPlayer)> {
+        let mut picks = Vec::new();
+        for draft in self.drafts.iter_mut().filter(|d| d.is_expired(now)) {
+            let captain = draft.current_captain();
+            if let Some(player) = draft.auto_pick() {
+                picks.push((draft.channel, captain, player));
+            }
+        }
+        picks
+    }
+    /// removes and returns every draft in which every player has been picked.
+    pub fn take_complete (&mut self) -> Vec<Draft> {
+        let mut complete = Vec::new();
+        let mut i = 0;
+        while i < self.drafts.len() {
+            if self.drafts[i].is_complete() {
+                complete.push(self.drafts.remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        complete
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players (count: u64) -> Vec<RatedPlayer> {
+        (1..=count)
+            .map(|i| RatedPlayer { discord_uuid: i, rating: 1000.0 + i as f64 * 10.0 })
+            .collect()
+    }
+
+    fn draft (count: u64) -> Draft {
+        let players = players(count);
+        let captains = choose_captains(&players, CaptainSelection::Highest);
+        Draft::construct("test", 0, &players, captains, Duration::from_secs(30))
+    }
+
+    #[test]
+    fn parses_team_selection() {
+        assert_eq!(TeamSelection::parse("").unwrap(), TeamSelection::Balance);
+        assert_eq!(TeamSelection::parse("draft").unwrap(), TeamSelection::Draft(CaptainSelection::Highest));
+        assert_eq!(TeamSelection::parse(" draft  random ").unwrap(), TeamSelection::Draft(CaptainSelection::Random));
+        assert!(TeamSelection::parse("draft worst").is_err());
+    }
+
+    #[test]
+    fn highest_rated_are_captains_and_weaker_picks_first() {
+        let draft = draft(6);
+        assert_eq!(draft.teams.axis[0].discord_uuid, 5);
+        assert_eq!(draft.teams.allies[0].discord_uuid, 6);
+        assert_eq!(draft.current_captain(), 5);
+    }
+
+    #[test]
+    fn random_captains_are_players() {
+        let players = players(12);
+        let (first, second) = choose_captains(&players, CaptainSelection::Random);
+        assert_ne!(first.discord_uuid, second.discord_uuid);
+        assert!(first.rating <= second.rating);
+    }
+
+    #[test]
+    fn picks_follow_abba_order() {
+        let mut draft = draft(12);
+        let order: Vec<Team> = (0..6)
+            .map(|_| {
+                let team = draft.current_team();
+                draft.auto_pick();
+                team
+            })
+            .collect();
+        assert_eq!(order, vec![Team::Axis, Team::Allies, Team::Allies, Team::Axis, Team::Axis, Team::Allies]);
+    }
+
+    #[test]
+    fn only_current_captain_can_pick_available_players() {
+        let mut draft = draft(6);
+        assert!(draft.pick(6, 1).is_err());
+        assert!(draft.pick(5, 5).is_err());
+        assert!(draft.pick(5, 9).is_err());
+        assert_eq!(draft.pick(5, 1).unwrap().discord_uuid, 1);
+        assert_eq!(draft.current_captain(), 6);
+    }
+
+    #[test]
+    fn last_player_is_assigned_automatically() {
+        let mut draft = draft(6);
+        draft.pick(5, 1).unwrap();
+        draft.pick(6, 2).unwrap();
+        draft.pick(6, 3).unwrap();
+        assert!(draft.is_complete());
+        assert_eq!(draft.teams.axis.len(), 3);
+        assert_eq!(draft.teams.allies.len(), 3);
+    }
+
+    #[test]
+    fn restored_draft_continues_with_the_right_captain() {
+        let mut draft = draft(8);
+        draft.pick(7, 1).unwrap();
+        draft.pick(8, 2).unwrap();
+        draft.pick(8, 3).unwrap();
+        let restored = Draft::restore("test", 0, &draft.teams, draft.pool(), Duration::from_secs(30)).unwrap();
+        assert_eq!(restored.teams.axis, draft.teams.axis);
+        assert_eq!(restored.teams.allies, draft.teams.allies);
+        assert_eq!(restored.pool(), draft.pool());
+        assert_eq!(restored.current_captain(), 7);
+        let empty = Teams { axis: Vec::new(), allies: draft.teams.allies.clone() };
+        assert!(Draft::restore("test", 0, &empty, draft.pool(), Duration::from_secs(30)).is_err());
+    }
+
+    #[test]
+    fn one_versus_one_needs_no_picks() {
+        assert!(draft(2).is_complete());
+    }
+
+    #[test]
+    fn expired_pick_takes_highest_rated() {
+        let mut drafts = Drafts::construct(HashMap::new(), Duration::from_secs(0));
+        let players = players(6);
+        let captains = choose_captains(&players, CaptainSelection::Highest);
+        drafts.add(Draft::construct("3v3", 0, &players, captains, Duration::from_secs(0)));
+        let picks = drafts.auto_pick_expired(SystemTime::now());
+        assert_eq!(picks.len(), 1);
+        assert_eq!(picks[0].1, 5);
+        assert_eq!(picks[0].2.discord_uuid, 4);
+    }
+}
@@ -0,0 +1,232 @@
+use std:: {
+    collections::HashMap,
+    time:: {
+        Duration,
+        SystemTime
+    }
+};
+
+/// interval between checks of subscription times against the database.
+const SWEEP: Duration = Duration::from_secs(60);
+
+/// Reason enumeration for why a subscribed player is asked whether they are
+/// still there
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reason {
+    /// has been waiting in a queue for longer than its time to live
+    Stale,
+    /// went offline on discord while waiting in a queue
+    Offline
+}
+
+/// Warning structure for a subscribed player about to be unsubscribed
+///
+/// # Members
+///
+///     ```
+///     player: discord uuid of the warned player
+///     groups: match making groups the player is unsubscribed from
+///     reason: why the player was warned
+///     message: discord message id of the warning, once sent
+///     deadline: time at which the player is unsubscribed
+///     ```
+pub struct Warning {
+    pub player: u64,
+    pub groups: Vec<String>,
+    pub reason: Reason,
+    pub message: Option<u64>,
+    pub deadline: SystemTime
+}
+
+/// Expiries structure tracking the subscriptions that are about to expire
+///
+/// # Members
+///
+///     ```
+///     ttl: how long a player may wait in a queue before being warned
+///     ttls: time to live of match making groups that override the default
+///     grace: how long a warned player has to say they are still there
+///     refreshed: time each player last said they are still there
+///     warnings: warnings waiting on an answer
+///     next_sweep: time at which subscription times are checked next
+///     ```
+pub struct Expiries {
+    ttl: Option<Duration>,
+    ttls: HashMap<String, Option<Duration>>,
+    pub grace: Duration,
+    refreshed: HashMap<u64, SystemTime>,
+    warnings: Vec<Warning>,
+    next_sweep: SystemTime
+}
+
+// Expiries implementation
+impl Expiries {
+    /// constructs the subscription expiries from the default time to live,
+    /// the time to live of groups that override it and the time warned
+    /// players have to answer. a time to live of zero never expires.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let expiries = expiry::Expiries::construct(ttl, ttls, Duration::from_secs(config.match_making.expiry_grace));
+    /// ```
+    pub fn construct (ttl: Duration, ttls: HashMap<String, Duration>, grace: Duration) -> Self {
+        let enabled = |t: Duration| if t.as_secs() == 0 { None } else { Some(t) };
+        Self {
+            ttl: enabled(ttl),
+            ttls: ttls.into_iter()
+                .map(|(g, t)| (g.to_lowercase(), enabled(t)))
+                .collect(),
+            grace,
+            refreshed: HashMap::new(),
+            warnings: Vec::new(),
+            next_sweep: SystemTime::now()
+        }
+    }
+    /// returns how long a player may wait in the queue of the specified match
+    /// making group, or None if subscriptions to it never expire.
+    pub fn ttl (&self, group: &str) -> Option<Duration> {
+        match self.ttls.get(&group.to_lowercase()) {
+            Some(t) => *t,
+            None => self.ttl
+        }
+    }
+    /// returns whether or not subscription times are due to be checked, and
+    /// schedules the next check if they are.
+    pub fn sweep_due (&mut self, now: SystemTime) -> bool {
+        if now < self.next_sweep {
+            return false;
+        }
+        self.next_sweep = now + SWEEP;
+        true
+    }
+    /// returns whether or not a player who subscribed to the specified match
+    /// making group at the specified time has waited longer than its time to
+    /// live, counting from when they last said they are still there. players
+    /// that were already warned are not stale again.
+    pub fn is_stale (&self, group: &str, player: u64, subscribed_at: SystemTime, now: SystemTime) -> bool {
+        let ttl = match self.ttl(group) {
+            Some(t) => t,
+            None => return false
+        };
+        if self.is_warned(player) {
+            return false;
+        }
+        let since = match self.refreshed.get(&player) {
+            Some(r) if *r > subscribed_at => *r,
+            _ => subscribed_at
+        };
+        now.duration_since(since).unwrap_or_default() >= ttl
+    }
+    /// returns whether or not a player is waiting on a warning.
+    pub fn is_warned (&self, player: u64) -> bool {
+        self.warnings.iter().any(|w| w.player == player)
+    }
+    /// warns a player that they are about to be unsubscribed from the
+    /// specified match making groups. returns None if the player was already
+    /// warned, otherwise the warning so the message it is sent as can be
+    /// recorded.
+    pub fn warn (&mut self, player: u64, groups: &[String], reason: Reason, now: SystemTime) -> Option<&mut Warning> {
+        if groups.is_empty() || self.is_warned(player) {
+            return None;
+        }
+        self.warnings.push(Warning {
+            player,
+            groups: groups.to_vec(),
+            reason,
+            message: None,
+            deadline: now + self.grace
+        });
+        self.warnings.last_mut()
+    }
+    /// returns the warning sent to a player as the specified discord message.
+    pub fn find_by_message (&self, message: u64, player: u64) -> Option<&Warning> {
+        self.warnings.iter().find(|w| w.message == Some(message) && w.player == player)
+    }
+    /// records that a player is still there, restarting the time to live of
+    /// their subscriptions. returns the warning they answered, if any.
+    pub fn refresh (&mut self, player: u64, now: SystemTime) -> Option<Warning> {
+        self.refreshed.insert(player, now);
+        let index = self.warnings.iter().position(|w| w.player == player)?;
+        Some(self.warnings.remove(index))
+    }
+    /// removes and returns every warning that has run out of time.
+    pub fn take_expired (&mut self, now: SystemTime) -> Vec<Warning> {
+        let (expired, warnings) = self.warnings.drain(..).partition(|w| now >= w.deadline);
+        self.warnings = warnings;
+        for warning in expired.iter() {
+            self.refreshed.remove(&warning.player);
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    fn expiries () -> Expiries {
+        let mut ttls = HashMap::new();
+        ttls.insert("1v1".to_string(), 10 * MINUTE);
+        ttls.insert("6v6".to_string(), Duration::from_secs(0));
+        Expiries::construct(60 * MINUTE, ttls, 5 * MINUTE)
+    }
+
+    #[test]
+    fn groups_override_the_default_ttl() {
+        let expiries = expiries();
+        assert_eq!(expiries.ttl("3v3"), Some(60 * MINUTE));
+        assert_eq!(expiries.ttl("1V1"), Some(10 * MINUTE));
+        assert_eq!(expiries.ttl("6v6"), None);
+    }
+
+    #[test]
+    fn subscriptions_go_stale_after_their_ttl() {
+        let expiries = expiries();
+        let start = SystemTime::UNIX_EPOCH;
+        assert!(!expiries.is_stale("1v1", 1, start, start + 9 * MINUTE));
+        assert!(expiries.is_stale("1v1", 1, start, start + 10 * MINUTE));
+        assert!(!expiries.is_stale("3v3", 1, start, start + 10 * MINUTE));
+        assert!(!expiries.is_stale("6v6", 1, start, start + 600 * MINUTE));
+    }
+
+    #[test]
+    fn answering_a_warning_restarts_the_ttl() {
+        let mut expiries = expiries();
+        let start = SystemTime::UNIX_EPOCH;
+        let now = start + 10 * MINUTE;
+        expiries.warn(1, &["1v1".to_string()], Reason::Stale, now).unwrap().message = Some(7);
+        assert!(!expiries.is_stale("1v1", 1, start, now));
+        assert!(expiries.warn(1, &["1v1".to_string()], Reason::Offline, now).is_none());
+        assert!(expiries.find_by_message(7, 2).is_none());
+        assert!(expiries.find_by_message(7, 1).is_some());
+        assert_eq!(expiries.refresh(1, now).unwrap().reason, Reason::Stale);
+        assert!(!expiries.is_warned(1));
+        assert!(!expiries.is_stale("1v1", 1, start, now + 9 * MINUTE));
+        assert!(expiries.is_stale("1v1", 1, start, now + 10 * MINUTE));
+    }
+
+    #[test]
+    fn unanswered_warnings_expire_after_the_grace_period() {
+        let mut expiries = expiries();
+        let now = SystemTime::UNIX_EPOCH;
+        expiries.warn(1, &["3v3".to_string()], Reason::Offline, now);
+        expiries.warn(2, &["3v3".to_string()], Reason::Offline, now + MINUTE);
+        assert!(expiries.take_expired(now + 4 * MINUTE).is_empty());
+        let expired = expiries.take_expired(now + 5 * MINUTE);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].player, 1);
+        assert!(expiries.is_warned(2));
+    }
+
+    #[test]
+    fn sweeps_are_spaced_out() {
+        let mut expiries = expiries();
+        let now = SystemTime::now();
+        assert!(expiries.sweep_due(now));
+        assert!(!expiries.sweep_due(now + Duration::from_secs(30)));
+        assert!(expiries.sweep_due(now + SWEEP));
+    }
+}
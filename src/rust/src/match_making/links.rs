@@ -0,0 +1,174 @@
+use et_mm_bot::et::query;
+use rand:: {
+    distributions::Alphanumeric,
+    Rng
+};
+use std:: {
+    collections::HashMap,
+    time:: {
+        Duration,
+        SystemTime
+    }
+};
+
+/// prefix of every link token, so tokens stand out in in-game chat.
+const TOKEN_PREFIX: &str = "link-";
+
+/// number of random characters following the token prefix.
+const TOKEN_LENGTH: usize = 6;
+
+/// how long a link token can be redeemed for after it was issued.
+pub const TOKEN_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Identity enumeration for the ways a player is recognised in game
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Identity {
+    /// the cl_guid of the player's client
+    Guid(String),
+    /// the name of the player without colour codes, for servers that do not
+    /// log guids
+    Name(String)
+}
+
+// Identity implementation
+impl Identity {
+    /// returns the identity of a client from its userinfo, preferring its
+    /// cl_guid over its name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let identity = links::Identity::from_userinfo(&info).unwrap();
+    /// ```
+    pub fn from_userinfo (info: &HashMap<String, String>) -> Option<Self> {
+        let guid = info.get("cl_guid")
+            .map(|g| g.trim().to_uppercase())
+            .filter(|g| !g.is_empty() && g != "UNKNOWN");
+        if let Some(g) = guid {
+            return Some(Identity::Guid(g));
+        }
+        info.get("n").and_then(|n| Self::from_name(n))
+    }
+    /// returns the identity of a client from its name including colour codes,
+    /// for clients whose userinfo is unknown.
+    pub fn from_name (name: &str) -> Option<Self> {
+        let name = query::strip_colors(name).trim().to_string();
+        if name.is_empty() {
+            return None;
+        }
+        Some(Identity::Name(name))
+    }
+    /// returns the database representation of the kind of identity.
+    pub fn kind (&self) -> &'static str {
+        match self {
+            Identity::Guid(_) => "guid",
+            Identity::Name(_) => "name"
+        }
+    }
+    /// returns the identity itself.
+    pub fn value (&self) -> &str {
+        match self {
+            Identity::Guid(v) | Identity::Name(v) => v
+        }
+    }
+}
+
+/// Links structure holding the link tokens that have not been redeemed yet
+///
+/// # Members
+///
+///     ```
+///     tokens: discord uuid and deadline of every outstanding token
+///     ```
+pub struct Links {
+    tokens: HashMap<String, (u64, SystemTime)>
+}
+
+// Links implementation
+impl Links {
+    /// constructs an empty set of link tokens.
+    pub fn construct () -> Self {
+        Self {
+            tokens: HashMap::new()
+        }
+    }
+    /// issues a new one-time token for the specified user, replacing any
+    /// token they were issued before. expired tokens are dropped as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let token = links.issue(discord_uuid, SystemTime::now());
+    /// ```
+    pub fn issue (&mut self, discord_uuid: u64, now: SystemTime) -> String {
+        self.tokens.retain(|_, (user, deadline)| *user != discord_uuid && *deadline > now);
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(TOKEN_LENGTH)
+            .collect();
+        let token = format!("{}{}", TOKEN_PREFIX, token.to_lowercase());
+        self.tokens.insert(token.clone(), (discord_uuid, now + TOKEN_TIMEOUT));
+        token
+    }
+    /// redeems the first outstanding token found in a chat message and
+    /// returns the user it was issued to. redeemed and expired tokens are
+    /// removed.
+    pub fn redeem (&mut self, text: &str, now: SystemTime) -> Option<u64> {
+        let text = query::strip_colors(text).to_lowercase();
+        for word in text.split_whitespace() {
+            if let Some((user, deadline)) = self.tokens.remove(word) {
+                if deadline > now {
+                    return Some(user);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn userinfo (pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn prefers_guid_over_name() {
+        let info = userinfo(&[("n", "^1red"), ("cl_guid", "abcdef0123")]);
+        assert_eq!(Identity::from_userinfo(&info), Some(Identity::Guid("ABCDEF0123".to_string())));
+        let info = userinfo(&[("n", "^1red^7name"), ("cl_guid", "unknown")]);
+        assert_eq!(Identity::from_userinfo(&info), Some(Identity::Name("redname".to_string())));
+        assert_eq!(Identity::from_userinfo(&userinfo(&[("n", "^1")])), None);
+    }
+
+    #[test]
+    fn tokens_are_redeemed_once() {
+        let now = SystemTime::now();
+        let mut links = Links::construct();
+        let token = links.issue(7, now);
+        assert!(token.starts_with(TOKEN_PREFIX));
+        let message = format!("my token is ^3{}", token.to_uppercase());
+        assert_eq!(links.redeem(&message, now), Some(7));
+        assert_eq!(links.redeem(&token, now), None);
+    }
+
+    #[test]
+    fn new_tokens_replace_old_ones() {
+        let now = SystemTime::now();
+        let mut links = Links::construct();
+        let old = links.issue(7, now);
+        let new = links.issue(7, now);
+        assert_eq!(links.redeem(&old, now), None);
+        assert_eq!(links.redeem(&new, now), Some(7));
+    }
+
+    #[test]
+    fn expired_tokens_are_not_redeemed() {
+        let now = SystemTime::now();
+        let mut links = Links::construct();
+        let token = links.issue(7, now);
+        assert_eq!(links.redeem(&token, now + TOKEN_TIMEOUT), None);
+    }
+}
@@ -0,0 +1,304 @@
+use rand::seq::SliceRandom;
+use std:: {
+    collections::HashMap,
+    error::Error,
+    time:: {
+        Duration,
+        SystemTime
+    }
+};
+
+/// smallest number of maps offered in a vote when recently played maps are
+/// excluded, as long as the map pool is large enough.
+const MINIMUM_CANDIDATES: usize = 2;
+
+/// returns the maps of a pool that may be voted on, leaving out recently
+/// played maps. recent maps are only left out while enough maps remain for a
+/// meaningful vote; the oldest recent maps are allowed back first.
+///
+/// # Example
+///
+/// ```
+/// let maps = map_vote::candidates(&pool, &["supply".to_string()]);
+/// ```
+pub fn candidates (pool: &[String], recent: &[String]) -> Vec<String> {
+    let minimum = MINIMUM_CANDIDATES.min(pool.len());
+    let mut excluded = recent.to_vec();
+    loop {
+        let maps: Vec<String> = pool.iter()
+            .filter(|m| !excluded.contains(m))
+            .cloned()
+            .collect();
+        if maps.len() >= minimum || excluded.is_empty() {
+            return maps;
+        }
+        // recent maps are ordered from most to least recently played
+        excluded.pop();
+    }
+}
+
+/// MapVote structure for players of a popped queue voting on a map
+///
+/// # Members
+///
+///     ```
+///     id: database id of the vote, once saved
+///     group: match making group the players were popped from
+///     channel: discord channel id the vote is running in
+///     message: discord message id of the vote, once posted
+///     players: players allowed to vote in their original queue order
+///     maps: maps that can be voted for
+///     votes: index of the map each player voted for
+///     deadline: time at which the vote closes
+///     ```
+pub struct MapVote {
+    pub id: Option<i32>,
+    pub group: String,
+    pub channel: u64,
+    pub message: Option<u64>,
+    players: Vec<u64>,
+    maps: Vec<String>,
+    votes: HashMap<u64, usize>,
+    pub deadline: SystemTime
+}
+
+// MapVote implementation
+impl MapVote {
+    /// constructs a map vote that closes after the specified timeout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vote = map_vote::MapVote::construct("6v6", channel, &players, &maps, Duration::from_secs(45));
+    /// ```
+    pub fn construct (group: &str, channel: u64, players: &[u64], maps: &[String], timeout: Duration) -> Self {
+        Self {
+            id: None,
+            group: group.to_string(),
+            channel,
+            message: None,
+            players: players.to_vec(),
+            maps: maps.to_vec(),
+            votes: HashMap::new(),
+            deadline: SystemTime::now() + timeout
+        }
+    }
+    /// returns the players allowed to vote in their original queue order.
+    pub fn players (&self) -> &[u64] {
+        &self.players
+    }
+    /// returns the maps that can be voted for.
+    pub fn maps (&self) -> &[String] {
+        &self.maps
+    }
+    /// returns the index of the map each player voted for.
+    pub fn votes (&self) -> &HashMap<u64, usize> {
+        &self.votes
+    }
+    /// returns whether or not the specified player may vote.
+    pub fn contains (&self, player: u64) -> bool {
+        self.players.contains(&player)
+    }
+    /// records the vote of a player for a map by name. a later vote by the
+    /// same player replaces the earlier one.
+    pub fn vote (&mut self, player: u64, map: &str) -> Result<(), Box<dyn Error>> {
+        let index = match self.maps.iter().position(|m| m.eq_ignore_ascii_case(map.trim())) {
+            Some(i) => i,
+            None => return Err(format!("`{}` is not in this vote. choose from: {}", map.trim(), self.maps.join(", ")).into())
+        };
+        self.vote_index(player, index)
+    }
+    /// records the vote of a player for a map by its position in the vote.
+    pub fn vote_index (&mut self, player: u64, index: usize) -> Result<(), Box<dyn Error>> {
+        if !self.contains(player) {
+            return Err("you are not part of this map vote.".into());
+        }
+        if index >= self.maps.len() {
+            return Err("that map is not in this vote.".into());
+        }
+        self.votes.insert(player, index);
+        Ok (())
+    }
+    /// returns the number of votes each map received, in the order of the maps.
+    pub fn tally (&self) -> Vec<usize> {
+        let mut tally = vec![0; self.maps.len()];
+        for index in self.votes.values() {
+            tally[*index] += 1;
+        }
+        tally
+    }
+    /// returns whether or not the vote is over, either because every player
+    /// voted or because it ran out of time.
+    pub fn is_finished (&self, now: SystemTime) -> bool {
+        self.votes.len() == self.players.len() || now >= self.deadline
+    }
+    /// returns the map with the most votes. ties, including a vote nobody
+    /// took part in, are broken randomly.
+    pub fn winner (&self) -> String {
+        let tally = self.tally();
+        let most = tally.iter().cloned().max().unwrap_or(0);
+        let leaders: Vec<&String> = self.maps.iter()
+            .zip(tally.iter())
+            .filter(|(_, votes)| **votes == most)
+            .map(|(map, _)| map)
+            .collect();
+        match leaders.choose(&mut rand::thread_rng()) {
+            Some(m) => m.to_string(),
+            None => String::new()
+        }
+    }
+}
+
+/// MapVotes structure holding every map vote in progress
+///
+/// # Members
+///
+///     ```
+///     pools: map pool of every match making group with one
+///     recent: number of recently played maps left out of the next vote
+///     timeout: how long a map vote runs for
+///     votes: map votes in progress
+///     ```
+pub struct MapVotes {
+    pools: HashMap<String, Vec<String>>,
+    pub recent: usize,
+    pub timeout: Duration,
+    votes: Vec<MapVote>
+}
+
+// MapVotes implementation
+impl MapVotes {
+    /// constructs an empty set of map votes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let votes = map_vote::MapVotes::construct(config.map_pools(), config.match_making.recent_maps, Duration::from_secs(config.match_making.vote_timeout));
+    /// ```
+    pub fn construct (pools: HashMap<String, Vec<String>>, recent: usize, timeout: Duration) -> Self {
+        Self {
+            pools,
+            recent,
+            timeout,
+            votes: Vec::new()
+        }
+    }
+    /// returns the map pool of the specified match making group, or None if
+    /// the group does not vote on maps.
+    pub fn pool (&self, group: &str) -> Option<&[String]> {
+        match self.pools.get(&group.to_lowercase()) {
+            Some(p) if !p.is_empty() => Some(p),
+            _ => None
+        }
+    }
+    /// adds a map vote.
+    pub fn add (&mut self, vote: MapVote) {
+        self.votes.push(vote);
+    }
+    /// returns every map vote in progress.
+    pub fn all_mut (&mut self) -> &mut [MapVote] {
+        &mut self.votes
+    }
+    /// returns the map vote the specified player is part of.
+    pub fn find (&mut self, player: u64) -> Option<&mut MapVote> {
+        self.votes.iter_mut().find(|v| v.contains(player))
+    }
+    /// returns the map vote posted as the specified discord message.
+    pub fn find_by_message (&mut self, message: u64) -> Option<&mut MapVote> {
+        self.votes.iter_mut().find(|v| v.message == Some(message))
+    }
+    /// removes and returns every map vote that is over.
+    pub fn take_finished (&mut self, now: SystemTime) -> Vec<MapVote> {
+        let mut finished = Vec::new();
+        let mut i = 0;
+        while i < self.votes.len() {
+            if self.votes[i].is_finished(now) {
+                finished.push(self.votes.remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maps (names: &[&str]) -> Vec<String> {
+        names.iter().map(|m| m.to_string()).collect()
+    }
+
+    fn vote () -> MapVote {
+        let pool = maps(&["supply", "goldrush", "radar"]);
+        MapVote::construct("3v3", 0, &[1, 2, 3, 4, 5, 6], &pool, Duration::from_secs(45))
+    }
+
+    #[test]
+    fn recent_maps_are_left_out() {
+        let pool = maps(&["supply", "goldrush", "radar", "frostbite", "braundorf_b4"]);
+        let recent = maps(&["radar", "supply"]);
+        assert_eq!(candidates(&pool, &recent), maps(&["goldrush", "frostbite", "braundorf_b4"]));
+    }
+
+    #[test]
+    fn small_pools_keep_a_choice() {
+        let pool = maps(&["supply", "goldrush", "radar"]);
+        let recent = maps(&["radar", "supply"]);
+        assert_eq!(candidates(&pool, &recent), maps(&["supply", "goldrush"]));
+    }
+
+    #[test]
+    fn single_map_pool_is_always_offered() {
+        let pool = maps(&["supply"]);
+        assert_eq!(candidates(&pool, &pool), pool);
+    }
+
+    #[test]
+    fn majority_wins() {
+        let mut vote = vote();
+        vote.vote(1, "radar").unwrap();
+        vote.vote(2, "RADAR").unwrap();
+        vote.vote(3, "supply").unwrap();
+        assert_eq!(vote.tally(), vec![1, 0, 2]);
+        assert_eq!(vote.winner(), "radar");
+    }
+
+    #[test]
+    fn players_can_change_their_vote() {
+        let mut vote = vote();
+        vote.vote(1, "radar").unwrap();
+        vote.vote_index(1, 1).unwrap();
+        assert_eq!(vote.tally(), vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn invalid_votes_are_rejected() {
+        let mut vote = vote();
+        assert!(vote.vote(1, "oasis").is_err());
+        assert!(vote.vote(7, "radar").is_err());
+        assert!(vote.vote_index(1, 3).is_err());
+    }
+
+    #[test]
+    fn ties_are_broken_between_leaders() {
+        let mut vote = vote();
+        vote.vote(1, "supply").unwrap();
+        vote.vote(2, "radar").unwrap();
+        for _ in 0..20 {
+            assert_ne!(vote.winner(), "goldrush");
+        }
+    }
+
+    #[test]
+    fn finishes_early_when_everyone_voted() {
+        let mut vote = vote();
+        for player in 1..=5 {
+            vote.vote(player, "supply").unwrap();
+        }
+        assert!(!vote.is_finished(SystemTime::now()));
+        vote.vote(6, "goldrush").unwrap();
+        assert!(vote.is_finished(SystemTime::now()));
+    }
+}
@@ -0,0 +1,532 @@
+use crate::match_making:: {
+    balance::Teams,
+    links::Identity,
+    penalties::Offence,
+    stats::MatchStats
+};
+use et_mm_bot::et::log:: {
+    Event,
+    Team as LogTeam
+};
+use std:: {
+    collections:: {
+        HashMap,
+        HashSet
+    },
+    error::Error
+};
+
+/// Team enumeration for the two sides of an ET match
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Team {
+    Axis,
+    Allies
+}
+
+// Team implementation
+impl Team {
+    /// returns the database representation of the team.
+    pub fn as_str (self) -> &'static str {
+        match self {
+            Team::Axis => "axis",
+            Team::Allies => "allies"
+        }
+    }
+    /// parses the database representation of a team.
+    pub fn parse (team: &str) -> Result<Self, Box<dyn Error>> {
+        match team.trim().to_lowercase().as_ref() {
+            "axis" => Ok (Team::Axis),
+            "allies" => Ok (Team::Allies),
+            _ => Err(format!("unknown team: {}", team).into())
+        }
+    }
+}
+
+/// MatchResult enumeration for the final result of a match
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchResult {
+    Axis,
+    Allies,
+    Draw
+}
+
+// MatchResult implementation
+impl MatchResult {
+    /// returns the database representation of the result.
+    pub fn as_str (self) -> &'static str {
+        match self {
+            MatchResult::Axis => "axis",
+            MatchResult::Allies => "allies",
+            MatchResult::Draw => "draw"
+        }
+    }
+    /// parses the database representation of a result.
+    pub fn parse (result: &str) -> Result<Self, Box<dyn Error>> {
+        match result.trim().to_lowercase().as_ref() {
+            "axis" => Ok (MatchResult::Axis),
+            "allies" => Ok (MatchResult::Allies),
+            "draw" => Ok (MatchResult::Draw),
+            _ => Err(format!("unknown result: `{}`. expected `axis`, `allies` or `draw`.", result).into())
+        }
+    }
+    /// returns the score of the axis team for rating purposes.
+    pub fn axis_score (self) -> f64 {
+        match self {
+            MatchResult::Axis => 1.0,
+            MatchResult::Allies => 0.0,
+            MatchResult::Draw => 0.5
+        }
+    }
+}
+
+/// Outcome enumeration for a result reported from the point of view of a team
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw
+}
+
+// Outcome implementation
+impl Outcome {
+    /// returns the reported representation of the outcome.
+    pub fn as_str (self) -> &'static str {
+        match self {
+            Outcome::Win => "win",
+            Outcome::Loss => "loss",
+            Outcome::Draw => "draw"
+        }
+    }
+    /// parses a reported outcome: `win`, `loss` or `draw`.
+    pub fn parse (outcome: &str) -> Result<Self, Box<dyn Error>> {
+        match outcome.trim().to_lowercase().as_ref() {
+            "win" => Ok (Outcome::Win),
+            "loss" => Ok (Outcome::Loss),
+            "draw" => Ok (Outcome::Draw),
+            _ => Err(format!("unknown outcome: `{}`. expected `win`, `loss` or `draw`.", outcome).into())
+        }
+    }
+    /// converts the outcome reported by a team into a match result.
+    pub fn result_for (self, team: Team) -> MatchResult {
+        match (self, team) {
+            (Outcome::Draw, _) => MatchResult::Draw,
+            (Outcome::Win, Team::Axis) | (Outcome::Loss, Team::Allies) => MatchResult::Axis,
+            (Outcome::Win, Team::Allies) | (Outcome::Loss, Team::Axis) => MatchResult::Allies
+        }
+    }
+}
+
+/// ReportStatus enumeration for the state of result reporting of a match
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportStatus {
+    Pending,
+    Confirmed(MatchResult),
+    Disputed
+}
+
+/// Match structure for a match that has been formed but not yet completed
+///
+/// # Members
+///
+///     ```
+///     id: database match id
+///     group: match making group the match was formed from
+///     channel: discord channel id the match was announced in
+///     teams: players of the match split into axis and allies
+///     server: name of the game server allocated to the match, if any
+///     logged_result: winner of the round being played, read from the server log
+///     rounds: rounds of the map being played, two for stopwatch maps
+///     rounds_played: rounds of the map finished so far
+///     stats: statistics of the players read from the server log
+///     live: whether the server log shows the match has started
+///     reports: latest result reported by each team
+///     departures: identities that left the server and have not come back
+///     abandon_reports: opponents that reported each player for abandoning
+///     ```
+pub struct Match {
+    pub id: i32,
+    pub group: String,
+    pub channel: u64,
+    pub teams: Teams,
+    pub server: Option<String>,
+    pub logged_result: Option<MatchResult>,
+    pub rounds: u32,
+    pub rounds_played: u32,
+    pub stats: MatchStats,
+    pub live: bool,
+    pub reports: HashMap<Team, MatchResult>,
+    departures: HashMap<Identity, Offence>,
+    abandon_reports: HashMap<u64, HashSet<u64>>
+}
+
+// Match implementation
+impl Match {
+    /// constructs a match with no reported results.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let game = matches::Match::construct(id, "3v3", channel, teams);
+    /// ```
+    pub fn construct (id: i32, group: &str, channel: u64, teams: Teams) -> Self {
+        Self {
+            id,
+            group: group.to_string(),
+            channel,
+            teams,
+            server: None,
+            logged_result: None,
+            rounds: 1,
+            rounds_played: 0,
+            stats: MatchStats::construct(),
+            live: false,
+            reports: HashMap::new(),
+            departures: HashMap::new(),
+            abandon_reports: HashMap::new()
+        }
+    }
+    /// returns the team of the specified player, or None if they are not
+    /// playing in this match.
+    pub fn team_of (&self, player: u64) -> Option<Team> {
+        if self.teams.axis.iter().any(|p| p.discord_uuid == player) {
+            Some(Team::Axis)
+        } else if self.teams.allies.iter().any(|p| p.discord_uuid == player) {
+            Some(Team::Allies)
+        } else {
+            None
+        }
+    }
+    /// follows the rounds played on the game server of the match from the
+    /// events of its log, returning the result of the match once the map
+    /// shuts down after its final round. stopwatch maps (`g_gametype` 3) are
+    /// played over two rounds with the teams swapping sides in between, so
+    /// the winner of the second round is logged by the side the team played
+    /// on in that round. the round being played is taken from
+    /// `g_currentround` when the server logs it, and counted otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// if let Some(result) = game.follow_round(&event) {
+    ///     lobby::complete_match(http, data, channel, game.id, result);
+    /// }
+    /// ```
+    pub fn follow_round (&mut self, event: &Event) -> Option<MatchResult> {
+        match event {
+            Event::InitGame(info) => {
+                self.rounds = match info.get("g_gametype").map(|g| g.as_str()) {
+                    Some("3") => 2,
+                    _ => 1
+                };
+                let round = info.iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case("g_currentround"))
+                    .and_then(|(_, v)| v.parse().ok());
+                if let Some(r) = round {
+                    self.rounds_played = r;
+                }
+                self.logged_result = None;
+                None
+            },
+            Event::Exit { winner: Some(LogTeam::Axis), .. } => {
+                self.logged_result = Some(MatchResult::Axis);
+                None
+            },
+            Event::Exit { winner: Some(LogTeam::Allies), .. } => {
+                self.logged_result = Some(MatchResult::Allies);
+                None
+            },
+            Event::ShutdownGame => {
+                // a map restart without a winner, such as the end of warmup,
+                // is not a round
+                let winner = self.logged_result.take()?;
+                self.rounds_played += 1;
+                if self.rounds_played < self.rounds {
+                    return None;
+                }
+                match (self.rounds_played % 2, winner) {
+                    (0, MatchResult::Axis) => Some(MatchResult::Allies),
+                    (0, MatchResult::Allies) => Some(MatchResult::Axis),
+                    _ => Some(winner)
+                }
+            },
+            _ => None
+        }
+    }
+    /// records the outcome reported by a player on behalf of their team. a
+    /// later report by the same team replaces the earlier one. once both teams
+    /// have reported the result is confirmed if they agree and disputed if not.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// match game.report(discord_uuid, Outcome::Win).unwrap() {
+    ///     ReportStatus::Confirmed(result) => (),
+    ///     _ => ()
+    /// }
+    /// ```
+    pub fn report (&mut self, player: u64, outcome: Outcome) -> Result<ReportStatus, Box<dyn Error>> {
+        let team = match self.team_of(player) {
+            Some(t) => t,
+            None => return Err(format!("player {} is not playing in match {}", player, self.id).into())
+        };
+        self.reports.insert(team, outcome.result_for(team));
+        let axis = self.reports.get(&Team::Axis);
+        let allies = self.reports.get(&Team::Allies);
+        match (axis, allies) {
+            (Some(a), Some(b)) if a == b => Ok (ReportStatus::Confirmed(*a)),
+            (Some(_), Some(_)) => Ok (ReportStatus::Disputed),
+            _ => Ok (ReportStatus::Pending)
+        }
+    }
+    /// records that the client with the specified identity left the game
+    /// server, which is leaving the match before it started or abandoning it
+    /// depending on whether the match is live.
+    pub fn depart (&mut self, identity: Identity) {
+        let offence = if self.live {
+            Offence::AbandonedMatch
+        } else {
+            Offence::LeftMatch
+        };
+        self.departures.entry(identity).or_insert(offence);
+    }
+    /// forgets the departure of a client that came back to the game server.
+    pub fn rejoin (&mut self, identity: &Identity) {
+        self.departures.remove(identity);
+    }
+    /// returns the identities that left the game server and did not come
+    /// back, along with their offence.
+    pub fn departures (&self) -> &HashMap<Identity, Offence> {
+        &self.departures
+    }
+    /// records a report by a player that an opponent abandoned the match.
+    /// returns true once at least half of the opposing team has reported the
+    /// player, which only happens once per player.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// if game.report_abandon(reporter, player).unwrap() {
+    ///     lobby::penalize(&context.http, &context.data, player, Offence::AbandonedMatch);
+    /// }
+    /// ```
+    pub fn report_abandon (&mut self, reporter: u64, player: u64) -> Result<bool, Box<dyn Error>> {
+        let (reporter_team, player_team) = match (self.team_of(reporter), self.team_of(player)) {
+            (Some(r), Some(p)) => (r, p),
+            _ => return Err(format!("both players must be playing in match {}", self.id).into())
+        };
+        if reporter_team == player_team {
+            return Err("only opponents can report a player for abandoning a match.".into());
+        }
+        let opponents = match reporter_team {
+            Team::Axis => self.teams.axis.len(),
+            Team::Allies => self.teams.allies.len()
+        };
+        let reporters = self.abandon_reports.entry(player).or_default();
+        Ok (reporters.insert(reporter) && reporters.len() == opponents.div_ceil(2))
+    }
+    /// returns whether enough opponents reported the specified player for
+    /// abandoning the match.
+    pub fn reported_abandoned (&self, player: u64) -> bool {
+        let opponents = match self.team_of(player) {
+            Some(Team::Axis) => self.teams.allies.len(),
+            Some(Team::Allies) => self.teams.axis.len(),
+            None => return false
+        };
+        self.abandon_reports.get(&player).map_or(0, |r| r.len()) >= opponents.div_ceil(2)
+    }
+}
+
+/// Matches structure holding every match that has not yet completed
+///
+/// # Members
+///
+///     ```
+///     matches: active matches
+///     ```
+pub struct Matches {
+    matches: Vec<Match>
+}
+
+// Matches implementation
+impl Matches {
+    /// constructs an empty set of active matches.
+    pub fn construct () -> Self {
+        Self {
+            matches: Vec::new()
+        }
+    }
+    /// adds a newly formed match.
+    pub fn add (&mut self, game: Match) {
+        self.matches.push(game);
+    }
+    /// returns every active match.
+    pub fn all (&self) -> &[Match] {
+        &self.matches
+    }
+    /// returns the active match the specified player is playing in.
+    pub fn find (&mut self, player: u64) -> Option<&mut Match> {
+        self.matches.iter_mut().find(|m| m.team_of(player).is_some())
+    }
+    /// returns the active match with the specified id.
+    pub fn find_by_id (&mut self, id: i32) -> Option<&mut Match> {
+        self.matches.iter_mut().find(|m| m.id == id)
+    }
+    /// returns the active match being played on the specified game server.
+    pub fn find_by_server (&mut self, server: &str) -> Option<&mut Match> {
+        self.matches.iter_mut().find(|m| m.server.as_deref() == Some(server))
+    }
+    /// removes and returns the active match with the specified id.
+    pub fn remove (&mut self, id: i32) -> Option<Match> {
+        let index = self.matches.iter().position(|m| m.id == id)?;
+        Some(self.matches.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::match_making::balance::RatedPlayer;
+
+    fn game () -> Match {
+        let teams = Teams {
+            axis: vec![RatedPlayer { discord_uuid: 1, rating: 1000.0 }],
+            allies: vec![RatedPlayer { discord_uuid: 2, rating: 1000.0 }]
+        };
+        Match::construct(7, "1v1", 0, teams)
+    }
+
+    /// feeds the events of a server log to the match, returning the results
+    /// of the match it reads at each map shutdown.
+    fn follow_log (game: &mut Match, log: &str) -> Vec<Option<MatchResult>> {
+        log.lines()
+            .filter_map(et_mm_bot::et::log::parse_line)
+            .filter_map(|event| {
+                let result = game.follow_round(&event);
+                match event {
+                    Event::ShutdownGame => Some(result),
+                    _ => None
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stopwatch_completes_after_second_round() {
+        let mut game = game();
+        let log = include_str!("../../tests/fixtures/stopwatch.log");
+        // warmup, first round, second round
+        assert_eq!(follow_log(&mut game, log), vec![None, None, Some(MatchResult::Allies)]);
+    }
+
+    #[test]
+    fn stopwatch_counts_rounds_without_current_round() {
+        let mut game = game();
+        let log = include_str!("../../tests/fixtures/stopwatch.log")
+            .replace("\\g_currentround\\0", "")
+            .replace("\\g_currentround\\1", "");
+        assert_eq!(follow_log(&mut game, &log), vec![None, None, Some(MatchResult::Allies)]);
+    }
+
+    #[test]
+    fn stopwatch_first_round_keeps_match_open() {
+        let mut game = game();
+        let log = include_str!("../../tests/fixtures/games.log");
+        assert_eq!(follow_log(&mut game, log), vec![None]);
+        assert_eq!(game.rounds_played, 1);
+    }
+
+    #[test]
+    fn objective_completes_after_first_round() {
+        let mut game = game();
+        let log = include_str!("../../tests/fixtures/games.log").replace("\\g_gametype\\3", "\\g_gametype\\2");
+        assert_eq!(follow_log(&mut game, &log), vec![Some(MatchResult::Axis)]);
+    }
+
+    #[test]
+    fn single_report_is_pending() {
+        let mut game = game();
+        assert_eq!(game.report(1, Outcome::Win).unwrap(), ReportStatus::Pending);
+    }
+
+    #[test]
+    fn agreeing_reports_are_confirmed() {
+        let mut game = game();
+        game.report(1, Outcome::Loss).unwrap();
+        assert_eq!(game.report(2, Outcome::Win).unwrap(), ReportStatus::Confirmed(MatchResult::Allies));
+    }
+
+    #[test]
+    fn agreeing_draws_are_confirmed() {
+        let mut game = game();
+        game.report(2, Outcome::Draw).unwrap();
+        assert_eq!(game.report(1, Outcome::Draw).unwrap(), ReportStatus::Confirmed(MatchResult::Draw));
+    }
+
+    #[test]
+    fn conflicting_reports_are_disputed() {
+        let mut game = game();
+        game.report(1, Outcome::Win).unwrap();
+        assert_eq!(game.report(2, Outcome::Win).unwrap(), ReportStatus::Disputed);
+    }
+
+    #[test]
+    fn team_can_correct_its_report() {
+        let mut game = game();
+        game.report(1, Outcome::Win).unwrap();
+        game.report(1, Outcome::Loss).unwrap();
+        assert_eq!(game.report(2, Outcome::Win).unwrap(), ReportStatus::Confirmed(MatchResult::Allies));
+    }
+
+    #[test]
+    fn results_are_parsed() {
+        assert_eq!(MatchResult::parse(" Allies ").unwrap(), MatchResult::Allies);
+        assert_eq!(MatchResult::parse(MatchResult::Draw.as_str()).unwrap(), MatchResult::Draw);
+        assert!(MatchResult::parse("win").is_err());
+    }
+
+    #[test]
+    fn outsiders_cannot_report() {
+        let mut game = game();
+        assert!(game.report(3, Outcome::Win).is_err());
+    }
+
+    fn three_a_side () -> Match {
+        let player = |discord_uuid| RatedPlayer { discord_uuid, rating: 1000.0 };
+        let teams = Teams {
+            axis: vec![player(1), player(2), player(3)],
+            allies: vec![player(4), player(5), player(6)]
+        };
+        Match::construct(8, "3v3", 0, teams)
+    }
+
+    #[test]
+    fn departures_depend_on_whether_the_match_is_live() {
+        let mut game = game();
+        game.depart(Identity::Name("early".to_string()));
+        game.live = true;
+        game.depart(Identity::Name("late".to_string()));
+        game.depart(Identity::Name("early".to_string()));
+        assert_eq!(game.departures().get(&Identity::Name("early".to_string())), Some(&Offence::LeftMatch));
+        assert_eq!(game.departures().get(&Identity::Name("late".to_string())), Some(&Offence::AbandonedMatch));
+        game.rejoin(&Identity::Name("late".to_string()));
+        assert_eq!(game.departures().len(), 1);
+    }
+
+    #[test]
+    fn abandoning_needs_half_of_the_opponents() {
+        let mut game = three_a_side();
+        assert!(!game.report_abandon(1, 4).unwrap());
+        assert!(!game.report_abandon(1, 4).unwrap());
+        assert!(!game.reported_abandoned(4));
+        assert!(game.report_abandon(2, 4).unwrap());
+        assert!(game.reported_abandoned(4));
+        assert!(!game.report_abandon(3, 4).unwrap());
+    }
+
+    #[test]
+    fn teammates_cannot_report_abandoning() {
+        let mut game = three_a_side();
+        assert!(game.report_abandon(1, 2).is_err());
+        assert!(game.report_abandon(1, 9).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+pub mod balance;
+pub mod draft;
+pub mod expiry;
+pub mod links;
+pub mod map_vote;
+pub mod matches;
+pub mod penalties;
+pub mod queue;
+pub mod rating;
+pub mod ready_check;
+pub mod servers;
+pub mod stats;
@@ -0,0 +1,268 @@
+use std:: {
+    error::Error,
+    time:: {
+        Duration,
+        SystemTime
+    }
+};
+
+/// queue bans given for a first, second and any further recent offence.
+const BANS: [Duration; 3] = [
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(30 * 60),
+    Duration::from_secs(24 * 60 * 60)
+];
+
+/// how long a player has to go without an offence for their next ban to
+/// drop back by one step.
+const DECAY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// longest ban an admin can give, which keeps its expiry within the range of
+/// both system times and the database.
+pub const MAX_BAN: Duration = Duration::from_secs(10 * 365 * 24 * 60 * 60);
+
+/// Offence enumeration for the ways a player can dodge or leave a match
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Offence {
+    /// did not ready up before the ready check ran out of time
+    MissedReadyCheck,
+    /// left the game server after it was allocated, before the match started
+    LeftMatch,
+    /// left the game server while the match was being played
+    AbandonedMatch,
+    /// banned by an admin, which does not escalate later bans
+    Banned
+}
+
+// Offence implementation
+impl Offence {
+    /// returns the database representation of the offence.
+    pub fn as_str (self) -> &'static str {
+        match self {
+            Offence::MissedReadyCheck => "ready_check",
+            Offence::LeftMatch => "left",
+            Offence::AbandonedMatch => "abandoned",
+            Offence::Banned => "banned"
+        }
+    }
+    /// parses the database representation of an offence.
+    pub fn parse (offence: &str) -> Result<Self, Box<dyn Error>> {
+        match offence.trim().to_lowercase().as_ref() {
+            "ready_check" => Ok (Offence::MissedReadyCheck),
+            "left" => Ok (Offence::LeftMatch),
+            "abandoned" => Ok (Offence::AbandonedMatch),
+            "banned" => Ok (Offence::Banned),
+            _ => Err(format!("unknown offence: {}", offence).into())
+        }
+    }
+    /// returns a description of the offence to show to players.
+    pub fn describe (self) -> &'static str {
+        match self {
+            Offence::MissedReadyCheck => "missed a ready check",
+            Offence::LeftMatch => "left a match before it started",
+            Offence::AbandonedMatch => "abandoned a match",
+            Offence::Banned => "was banned by an admin"
+        }
+    }
+}
+
+/// Penalty structure for a queue ban given to a player
+///
+/// # Members
+///
+///     ```
+///     offence: offence the ban was given for
+///     created_at: time the offence was committed
+///     expires_at: time the player may queue again
+///     ```
+#[derive(Clone, Debug)]
+pub struct Penalty {
+    pub offence: Offence,
+    pub created_at: SystemTime,
+    pub expires_at: SystemTime
+}
+
+/// returns the number of ban steps a player has built up at the specified
+/// time. every offence adds a step and every full decay period without an
+/// offence removes one. bans given by admins are left out.
+fn level (penalties: &[Penalty], now: SystemTime) -> usize {
+    let decay = |level: usize, since: Option<SystemTime>, until: SystemTime| match since {
+        Some(s) => {
+            let quiet = until.duration_since(s).unwrap_or_default();
+            level.saturating_sub((quiet.as_secs() / DECAY.as_secs()) as usize)
+        },
+        None => level
+    };
+    let mut level = 0;
+    let mut last = None;
+    for penalty in penalties.iter().filter(|p| p.offence != Offence::Banned) {
+        level = decay(level, last, penalty.created_at) + 1;
+        last = Some(penalty.created_at);
+    }
+    decay(level, last, now)
+}
+
+/// returns how long a player with the specified past penalties, oldest first,
+/// is banned for a new offence committed at the specified time.
+///
+/// # Example
+///
+/// ```
+/// let ban = penalties::ban_duration(&[], SystemTime::now());
+/// assert_eq!(ban, Duration::from_secs(300));
+/// ```
+pub fn ban_duration (penalties: &[Penalty], now: SystemTime) -> Duration {
+    BANS[level(penalties, now).min(BANS.len() - 1)]
+}
+
+/// returns the penalty a player is currently banned by, which is the one
+/// that expires last, or None if they may queue.
+///
+/// # Example
+///
+/// ```
+/// if let Some(penalty) = penalties::active(&penalties, SystemTime::now()) {
+///     println!("banned for having {}", penalty.offence.describe());
+/// }
+/// ```
+pub fn active (penalties: &[Penalty], now: SystemTime) -> Option<&Penalty> {
+    penalties.iter()
+        .filter(|p| p.expires_at > now)
+        .max_by_key(|p| p.expires_at)
+}
+
+/// formats a duration in whole hours and minutes, rounding up to the next
+/// minute.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(penalties::format_duration(Duration::from_secs(5430)), "1 hour 31 minutes");
+/// ```
+pub fn format_duration (duration: Duration) -> String {
+    let minutes = duration.as_secs().div_ceil(60);
+    let plural = |n: u64, unit: &str| format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" });
+    match (minutes / 60, minutes % 60) {
+        (0, m) => plural(m, "minute"),
+        (h, 0) => plural(h, "hour"),
+        (h, m) => format!("{} {}", plural(h, "hour"), plural(m, "minute"))
+    }
+}
+
+/// parses a ban length given by an admin, which is a number of minutes,
+/// hours or days such as `30m`, `2h` or `1d`. a bare number is in minutes.
+/// lengths over MAX_BAN are refused.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(penalties::parse_duration("2h").unwrap(), Duration::from_secs(7200));
+/// ```
+pub fn parse_duration (duration: &str) -> Result<Duration, Box<dyn Error>> {
+    let duration = duration.trim().to_lowercase();
+    let invalid = || format!("unknown ban length: `{}`. expected minutes, hours or days such as `30m`, `2h` or `1d`.", duration);
+    let (amount, unit) = match duration.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => duration.split_at(i),
+        None => (duration.as_str(), "m")
+    };
+    let seconds = match unit {
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(invalid().into())
+    };
+    match amount.parse::<u64>().ok().filter(|a| *a > 0).map(|a| a.checked_mul(seconds)) {
+        Some(Some(s)) if Duration::from_secs(s) <= MAX_BAN => Ok (Duration::from_secs(s)),
+        Some(_) => Err(format!("ban length too long: `{}`. bans last at most {} days.", duration, MAX_BAN.as_secs() / (24 * 60 * 60)).into()),
+        None => Err(invalid().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn penalty (created_at: SystemTime) -> Penalty {
+        Penalty {
+            offence: Offence::MissedReadyCheck,
+            created_at,
+            expires_at: created_at + BANS[0]
+        }
+    }
+
+    #[test]
+    fn bans_escalate_with_recent_offences() {
+        let start = SystemTime::UNIX_EPOCH + DECAY;
+        let mut penalties = Vec::new();
+        assert_eq!(ban_duration(&penalties, start), Duration::from_secs(300));
+        penalties.push(penalty(start));
+        assert_eq!(ban_duration(&penalties, start + HOUR), Duration::from_secs(1800));
+        penalties.push(penalty(start + HOUR));
+        assert_eq!(ban_duration(&penalties, start + 2 * HOUR), DAY);
+        penalties.push(penalty(start + 2 * HOUR));
+        assert_eq!(ban_duration(&penalties, start + 3 * HOUR), DAY);
+    }
+
+    #[test]
+    fn bans_decay_one_step_per_quiet_period() {
+        let start = SystemTime::UNIX_EPOCH;
+        let penalties = vec![penalty(start), penalty(start + HOUR), penalty(start + 2 * HOUR)];
+        assert_eq!(ban_duration(&penalties, start + 2 * HOUR + DECAY), DAY);
+        assert_eq!(ban_duration(&penalties, start + 2 * HOUR + 2 * DECAY), Duration::from_secs(1800));
+        assert_eq!(ban_duration(&penalties, start + 2 * HOUR + 3 * DECAY), Duration::from_secs(300));
+        assert_eq!(ban_duration(&penalties, start + 2 * HOUR + 10 * DECAY), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn admin_bans_do_not_escalate() {
+        let now = SystemTime::UNIX_EPOCH + DAY;
+        let mut ban = penalty(now - HOUR);
+        ban.offence = Offence::Banned;
+        assert_eq!(ban_duration(&[ban], now), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn active_penalty_expires_last() {
+        let now = SystemTime::UNIX_EPOCH + DAY;
+        let mut long = penalty(now - HOUR);
+        long.expires_at = now + HOUR;
+        let penalties = vec![penalty(now - DAY), long, penalty(now - Duration::from_secs(60))];
+        assert_eq!(active(&penalties, now).unwrap().expires_at, now + HOUR);
+        assert!(active(&penalties, now + 2 * HOUR).is_none());
+    }
+
+    #[test]
+    fn offences_round_trip() {
+        for offence in [Offence::MissedReadyCheck, Offence::LeftMatch, Offence::AbandonedMatch, Offence::Banned].iter() {
+            assert_eq!(Offence::parse(offence.as_str()).unwrap(), *offence);
+        }
+        assert!(Offence::parse("afk").is_err());
+    }
+
+    #[test]
+    fn durations_are_formatted_in_hours_and_minutes() {
+        assert_eq!(format_duration(Duration::from_secs(1)), "1 minute");
+        assert_eq!(format_duration(Duration::from_secs(300)), "5 minutes");
+        assert_eq!(format_duration(DAY), "24 hours");
+        assert_eq!(format_duration(HOUR + Duration::from_secs(61)), "1 hour 2 minutes");
+    }
+
+    #[test]
+    fn ban_lengths_are_parsed() {
+        assert_eq!(parse_duration("45").unwrap(), Duration::from_secs(45 * 60));
+        assert_eq!(parse_duration("30m").unwrap(), Duration::from_secs(30 * 60));
+        assert_eq!(parse_duration("2H").unwrap(), 2 * HOUR);
+        assert_eq!(parse_duration("1d").unwrap(), DAY);
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("1w").is_err());
+        assert!(parse_duration("h").is_err());
+        assert_eq!(parse_duration("3650d").unwrap(), MAX_BAN);
+        assert!(parse_duration("3651d").is_err());
+        assert!(parse_duration("100000000000d").is_err());
+        assert!(parse_duration("999999999999999d").is_err());
+        assert!(parse_duration("99999999999999999999d").is_err());
+    }
+}
@@ -0,0 +1,297 @@
+use std:: {
+    collections::HashMap,
+    error::Error
+};
+
+/// derives the number of players needed to fill a match making group from its
+/// name. group names are expected to be in the form of `<n>v<n>`, so `1v1`
+/// needs two players, `3v3` needs six players and `6v6` needs twelve.
+///
+/// # Example
+///
+/// ```
+/// let capacity = queue::capacity("3v3").unwrap();
+/// assert_eq!(capacity, 6);
+/// ```
+pub fn capacity (group: &str) -> Result<usize, Box<dyn Error>> {
+    let teams: Vec<&str> = group.split('v').collect();
+    if teams.len() < 2 {
+        return Err(format!("match making group name is not in the form <n>v<n>: {}", group).into());
+    }
+    let mut capacity: usize = 0;
+    for team in teams.iter() {
+        match team.trim().parse::<usize>() {
+            Ok (0) => return Err(format!("match making group has an empty team: {}", group).into()),
+            Ok (n) => capacity += n,
+            Err(_) => return Err(format!("match making group name is not in the form <n>v<n>: {}", group).into())
+        };
+    }
+    Ok (capacity)
+}
+
+/// Queue structure for a single match making group
+///
+/// # Members
+///
+///     ```
+///     group: match making group name
+///     capacity: number of players required for the queue to pop
+///     players: discord uuids of subscribed players in order of subscription
+///     ```
+pub struct Queue {
+    pub group: String,
+    pub capacity: usize,
+    players: Vec<u64>
+}
+
+// Queue implementation
+impl Queue {
+    /// constructs an empty queue for the specified match making group. the
+    /// capacity is derived from the group name unless one is specified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let queue = queue::Queue::construct("6v6", None).unwrap();
+    /// ```
+    pub fn construct (group: &str, capacity: Option<usize>) -> Result<Self, Box<dyn Error>> {
+        let capacity = match capacity {
+            Some(c) => c,
+            None => self::capacity(group)?
+        };
+        Ok (
+            Self {
+                group: group.to_lowercase(),
+                capacity,
+                players: Vec::new()
+            }
+        )
+    }
+    /// returns whether or not the specified player is waiting in the queue.
+    pub fn contains (&self, player: u64) -> bool {
+        self.players.contains(&player)
+    }
+    /// adds a player to the back of the queue. returns false if the player
+    /// is already waiting in the queue.
+    pub fn add (&mut self, player: u64) -> bool {
+        if self.contains(player) {
+            return false;
+        }
+        self.players.push(player);
+        true
+    }
+    /// removes a player from the queue. returns false if the player was not
+    /// waiting in the queue.
+    pub fn remove (&mut self, player: u64) -> bool {
+        let length = self.players.len();
+        self.players.retain(|p| *p != player);
+        length != self.players.len()
+    }
+    /// puts players back at the front of the queue in the order given, ahead
+    /// of anyone who subscribed after them. players already waiting in the
+    /// queue are left where they are.
+    pub fn requeue (&mut self, players: &[u64]) {
+        let returning: Vec<u64> = players.iter()
+            .filter(|p| !self.contains(**p))
+            .cloned()
+            .collect();
+        self.players.splice(0..0, returning);
+    }
+    /// pops the queue if it has reached capacity, returning the players at
+    /// the front of the queue. returns None if the queue is not yet full.
+    pub fn pop (&mut self) -> Option<Vec<u64>> {
+        if self.players.len() < self.capacity {
+            return None;
+        }
+        Some(self.players.drain(..self.capacity).collect())
+    }
+}
+
+/// Queues structure holding the queue of every match making group
+///
+/// # Members
+///
+///     ```
+///     queues: queues keyed by match making group name
+///     ```
+pub struct Queues {
+    queues: HashMap<String, Queue>
+}
+
+// Queues implementation
+impl Queues {
+    /// constructs an empty queue for every configured match making group,
+    /// with the capacity of groups that set one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let queues = queue::Queues::construct(&config.group_names(), &config.capacities()).unwrap();
+    /// ```
+    pub fn construct (groups: &[String], capacities: &HashMap<String, usize>) -> Result<Self, Box<dyn Error>> {
+        let mut queues = HashMap::new();
+        for group in groups.iter() {
+            let queue = Queue::construct(group, capacities.get(group).cloned())?;
+            queues.insert(queue.group.clone(), queue);
+        }
+        Ok (
+            Self {
+                queues
+            }
+        )
+    }
+    /// returns the number of players needed for the queue of the specified
+    /// match making group to pop.
+    pub fn capacity (&self, group: &str) -> Option<usize> {
+        self.queues.get(&group.to_lowercase()).map(|q| q.capacity)
+    }
+    /// returns the name of every match making group, smallest first.
+    pub fn groups (&self) -> Vec<String> {
+        let mut queues: Vec<&Queue> = self.queues.values().collect();
+        queues.sort_by(|a, b| a.capacity.cmp(&b.capacity).then_with(|| a.group.cmp(&b.group)));
+        queues.iter().map(|q| q.group.clone()).collect()
+    }
+    /// returns the name of every match making group the specified player is
+    /// waiting in, smallest first.
+    pub fn subscriptions (&self, player: u64) -> Vec<String> {
+        self.groups().into_iter()
+            .filter(|g| self.queues[g].contains(player))
+            .collect()
+    }
+    /// adds a player to the queue of the specified match making group without
+    /// popping it. this is used to restore subscriptions from the database on
+    /// startup; an overfull queue pops on the next subscription.
+    pub fn restore (&mut self, group: &str, player: u64) -> Result<(), Box<dyn Error>> {
+        match self.queues.get_mut(&group.to_lowercase()) {
+            Some(q) => q.add(player),
+            None => return Err(format!("no queue exists for match making group: {}", group).into())
+        };
+        Ok (())
+    }
+    /// adds a player to the queue of the specified match making group. if the
+    /// queue reaches capacity it pops, and every popped player is removed from
+    /// all other queues they are waiting in. returns the popped players.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// if let Some(players) = queues.subscribe("1v1", discord_uuid).unwrap() {
+    ///     // announce match
+    /// }
+    /// ```
+    pub fn subscribe (&mut self, group: &str, player: u64) -> Result<Option<Vec<u64>>, Box<dyn Error>> {
+        let queue = match self.queues.get_mut(&group.to_lowercase()) {
+            Some(q) => q,
+            None => return Err(format!("no queue exists for match making group: {}", group).into())
+        };
+        queue.add(player);
+        let players = match queue.pop() {
+            Some(p) => p,
+            None => return Ok (None)
+        };
+        for player in players.iter() {
+            self.remove_everywhere(*player);
+        }
+        Ok (Some(players))
+    }
+    /// removes a player from the queue of the specified match making group.
+    /// returns false if the player was not waiting in the queue.
+    pub fn unsubscribe (&mut self, group: &str, player: u64) -> bool {
+        match self.queues.get_mut(&group.to_lowercase()) {
+            Some(q) => q.remove(player),
+            None => false
+        }
+    }
+    /// removes a player from every queue, returning the names of the groups
+    /// they were removed from.
+    pub fn remove_everywhere (&mut self, player: u64) -> Vec<String> {
+        let mut groups = Vec::new();
+        for queue in self.queues.values_mut() {
+            if queue.remove(player) {
+                groups.push(queue.group.clone());
+            }
+        }
+        groups
+    }
+    /// puts players back at the front of the queue of the specified match
+    /// making group, for example after a ready check failed because of other
+    /// players. the queue pops again if it is still full. returns the popped
+    /// players.
+    pub fn requeue (&mut self, group: &str, players: &[u64]) -> Result<Option<Vec<u64>>, Box<dyn Error>> {
+        let queue = match self.queues.get_mut(&group.to_lowercase()) {
+            Some(q) => q,
+            None => return Err(format!("no queue exists for match making group: {}", group).into())
+        };
+        queue.requeue(players);
+        let players = match queue.pop() {
+            Some(p) => p,
+            None => return Ok (None)
+        };
+        for player in players.iter() {
+            self.remove_everywhere(*player);
+        }
+        Ok (Some(players))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queues () -> Queues {
+        let groups = vec!["1v1".to_string(), "3v3".to_string()];
+        Queues::construct(&groups, &HashMap::new()).unwrap()
+    }
+
+    #[test]
+    fn groups_are_listed_smallest_first() {
+        let groups = vec!["6v6".to_string(), "1v1".to_string(), "10v10".to_string(), "3v3".to_string()];
+        let queues = Queues::construct(&groups, &HashMap::new()).unwrap();
+        assert_eq!(queues.groups(), vec!["1v1", "3v3", "6v6", "10v10"]);
+    }
+
+    #[test]
+    fn configured_capacity_overrides_group_name() {
+        let groups = vec!["3v3".to_string(), "ffa".to_string()];
+        let mut capacities = HashMap::new();
+        capacities.insert("ffa".to_string(), 8);
+        let queues = Queues::construct(&groups, &capacities).unwrap();
+        assert_eq!(queues.capacity("FFA"), Some(8));
+        assert_eq!(queues.capacity("3v3"), Some(6));
+        assert_eq!(queues.capacity("6v6"), None);
+        assert!(Queues::construct(&groups, &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn capacity_is_derived_from_group_name() {
+        assert_eq!(capacity("1v1").unwrap(), 2);
+        assert_eq!(capacity("3v3").unwrap(), 6);
+        assert_eq!(capacity("6v6").unwrap(), 12);
+        assert!(capacity("pub").is_err());
+        assert!(capacity("0v3").is_err());
+    }
+
+    #[test]
+    fn full_queue_pops_and_clears_other_queues() {
+        let mut queues = queues();
+        queues.subscribe("3v3", 1).unwrap();
+        queues.subscribe("1v1", 1).unwrap();
+        assert_eq!(queues.subscriptions(1), vec!["1v1", "3v3"]);
+        let popped = queues.subscribe("1v1", 2).unwrap();
+        assert_eq!(popped, Some(vec![1, 2]));
+        assert!(queues.subscriptions(1).is_empty());
+        assert!(!queues.unsubscribe("3v3", 1));
+    }
+
+    #[test]
+    fn requeued_players_keep_their_positions() {
+        let mut queues = queues();
+        for player in 1..=6 {
+            queues.subscribe("3v3", player).unwrap();
+        }
+        queues.subscribe("3v3", 7).unwrap();
+        // players 2 and 5 failed the ready check
+        assert_eq!(queues.requeue("3v3", &[1, 3, 4, 6]).unwrap(), None);
+        assert_eq!(queues.subscribe("3v3", 8).unwrap(), Some(vec![1, 3, 4, 6, 7, 8]));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::match_making::balance:: {
+    self,
+    RatedPlayer,
+    Teams
+};
+
+/// rating assigned to players that have not played in a match making group.
+/// this must match the default of the `rating` column in `match_making_users`.
+pub const DEFAULT_RATING: f64 = 1000.0;
+
+/// fraction of its distance from the group mean that a rating keeps when a
+/// new season starts.
+pub const SEASON_RESET: f64 = 0.5;
+
+/// largest rating change a player can receive from a single match.
+const K_FACTOR: f64 = 32.0;
+
+/// rating difference at which the stronger side is expected to win ten times
+/// as often as the weaker side.
+const SCALE: f64 = 400.0;
+
+/// returns the expected score (win probability, counting draws as half a win)
+/// of a side rated `rating` against a side rated `opponent`.
+///
+/// # Example
+///
+/// ```
+/// let expected = rating::expected_score(1200.0, 1000.0);
+/// assert!(expected > 0.75);
+/// ```
+pub fn expected_score (rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / SCALE))
+}
+
+/// returns the teams of a match with updated ratings for a given axis score,
+/// where 1.0 is an axis win, 0.0 is an allies win and 0.5 is a draw. team
+/// strength is the average rating of its players and every player on a team
+/// receives the same rating change.
+///
+/// # Example
+///
+/// ```
+/// let updated = rating::update(&teams, MatchResult::Axis.axis_score());
+/// ```
+pub fn update (teams: &Teams, axis_score: f64) -> Teams {
+    let axis = balance::average(&teams.axis);
+    let allies = balance::average(&teams.allies);
+    let change = K_FACTOR * (axis_score - expected_score(axis, allies));
+    Teams {
+        axis: adjust(&teams.axis, change),
+        allies: adjust(&teams.allies, -change)
+    }
+}
+
+/// applies a rating change to every player of a team.
+fn adjust (players: &[RatedPlayer], change: f64) -> Vec<RatedPlayer> {
+    players.iter()
+        .map(|p| RatedPlayer { discord_uuid: p.discord_uuid, rating: p.rating + change })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team (ratings: &[f64]) -> Vec<RatedPlayer> {
+        ratings.iter()
+            .enumerate()
+            .map(|(i, r)| RatedPlayer { discord_uuid: i as u64, rating: *r })
+            .collect()
+    }
+
+    #[test]
+    fn equal_ratings_are_even() {
+        assert!((expected_score(DEFAULT_RATING, DEFAULT_RATING) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn expected_scores_are_complementary() {
+        let expected = expected_score(1350.0, 1100.0);
+        assert!(expected > 0.5);
+        assert!((expected + expected_score(1100.0, 1350.0) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scale_difference_is_ten_to_one() {
+        assert!((expected_score(1400.0, 1000.0) - 10.0 / 11.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn even_win_gains_half_k() {
+        let teams = Teams { axis: team(&[1000.0, 1000.0]), allies: team(&[1000.0, 1000.0]) };
+        let updated = update(&teams, 1.0);
+        assert!(updated.axis.iter().all(|p| (p.rating - 1016.0).abs() < 1e-9));
+        assert!(updated.allies.iter().all(|p| (p.rating - 984.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn even_draw_changes_nothing() {
+        let teams = Teams { axis: team(&[1100.0]), allies: team(&[1100.0]) };
+        let updated = update(&teams, 0.5);
+        assert!((updated.axis[0].rating - 1100.0).abs() < 1e-9);
+        assert!((updated.allies[0].rating - 1100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn upset_moves_more_than_expected_win() {
+        let teams = Teams { axis: team(&[1400.0]), allies: team(&[1000.0]) };
+        let expected = update(&teams, 1.0).axis[0].rating - 1400.0;
+        let upset = 1000.0 - update(&teams, 0.0).allies[0].rating;
+        assert!(expected > 0.0);
+        assert!(upset < 0.0);
+        assert!(upset.abs() > expected.abs());
+    }
+
+    #[test]
+    fn rating_is_conserved_for_equal_team_sizes() {
+        let teams = Teams { axis: team(&[1200.0, 900.0, 1000.0]), allies: team(&[1100.0, 1050.0, 980.0]) };
+        let before: f64 = teams.axis.iter().chain(teams.allies.iter()).map(|p| p.rating).sum();
+        let updated = update(&teams, 0.0);
+        let after: f64 = updated.axis.iter().chain(updated.allies.iter()).map(|p| p.rating).sum();
+        assert!((before - after).abs() < 1e-9);
+    }
+}
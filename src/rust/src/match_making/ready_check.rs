@@ -0,0 +1,211 @@
+use std:: {
+    collections::HashSet,
+    time:: {
+        Duration,
+        SystemTime
+    }
+};
+
+/// ReadyCheck structure for a popped queue waiting on its players
+///
+/// # Members
+///
+///     ```
+///     id: database id of the ready check, once saved
+///     group: match making group the queue popped from
+///     channel: discord channel id the ready check was posted in
+///     message: discord message id of the ready check, once posted
+///     players: popped players in their original queue order
+///     ready: players that have acknowledged the ready check
+///     deadline: time at which players that are not ready are dropped
+///     ```
+pub struct ReadyCheck {
+    pub id: Option<i32>,
+    pub group: String,
+    pub channel: u64,
+    pub message: Option<u64>,
+    players: Vec<u64>,
+    ready: HashSet<u64>,
+    pub deadline: SystemTime
+}
+
+// ReadyCheck implementation
+impl ReadyCheck {
+    /// constructs a ready check for popped players that expires after the
+    /// specified timeout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let check = ready_check::ReadyCheck::construct("3v3", channel, &players, Duration::from_secs(60));
+    /// ```
+    pub fn construct (group: &str, channel: u64, players: &[u64], timeout: Duration) -> Self {
+        Self {
+            id: None,
+            group: group.to_string(),
+            channel,
+            message: None,
+            players: players.to_vec(),
+            ready: HashSet::new(),
+            deadline: SystemTime::now() + timeout
+        }
+    }
+    /// returns the players of the ready check in their original queue order.
+    pub fn players (&self) -> &[u64] {
+        &self.players
+    }
+    /// returns whether or not the specified player was popped into this check.
+    pub fn contains (&self, player: u64) -> bool {
+        self.players.contains(&player)
+    }
+    /// marks a player as ready. returns false if the player is not part of
+    /// the ready check or was already ready.
+    pub fn ready (&mut self, player: u64) -> bool {
+        self.contains(player) && self.ready.insert(player)
+    }
+    /// returns whether or not every player is ready.
+    pub fn is_complete (&self) -> bool {
+        self.players.iter().all(|p| self.ready.contains(p))
+    }
+    /// returns whether or not the ready check has run out of time.
+    pub fn is_expired (&self, now: SystemTime) -> bool {
+        now >= self.deadline
+    }
+    /// returns the players that are ready in their original queue order.
+    pub fn ready_players (&self) -> Vec<u64> {
+        self.players.iter()
+            .filter(|p| self.ready.contains(p))
+            .cloned()
+            .collect()
+    }
+    /// returns the players that are not ready in their original queue order.
+    pub fn missing_players (&self) -> Vec<u64> {
+        self.players.iter()
+            .filter(|p| !self.ready.contains(p))
+            .cloned()
+            .collect()
+    }
+}
+
+/// ReadyChecks structure holding every ready check in progress
+///
+/// # Members
+///
+///     ```
+///     timeout: how long players have to acknowledge a ready check
+///     checks: ready checks in progress
+///     ```
+pub struct ReadyChecks {
+    pub timeout: Duration,
+    checks: Vec<ReadyCheck>
+}
+
+// ReadyChecks implementation
+impl ReadyChecks {
+    /// constructs an empty set of ready checks with the specified timeout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let checks = ready_check::ReadyChecks::construct(Duration::from_secs(config.match_making.ready_timeout));
+    /// ```
+    pub fn construct (timeout: Duration) -> Self {
+        Self {
+            timeout,
+            checks: Vec::new()
+        }
+    }
+    /// adds a ready check.
+    pub fn add (&mut self, check: ReadyCheck) {
+        self.checks.push(check);
+    }
+    /// returns every ready check in progress.
+    pub fn all_mut (&mut self) -> &mut [ReadyCheck] {
+        &mut self.checks
+    }
+    /// returns the ready check the specified player is part of.
+    pub fn find (&mut self, player: u64) -> Option<&mut ReadyCheck> {
+        self.checks.iter_mut().find(|c| c.contains(player))
+    }
+    /// returns the ready check posted as the specified discord message.
+    pub fn find_by_message (&mut self, message: u64) -> Option<&mut ReadyCheck> {
+        self.checks.iter_mut().find(|c| c.message == Some(message))
+    }
+    /// removes and returns every ready check that is complete.
+    pub fn take_complete (&mut self) -> Vec<ReadyCheck> {
+        self.take(|c| c.is_complete())
+    }
+    /// removes and returns every incomplete ready check that has run out of time.
+    pub fn take_expired (&mut self, now: SystemTime) -> Vec<ReadyCheck> {
+        self.take(|c| !c.is_complete() && c.is_expired(now))
+    }
+    /// removes and returns every ready check matching the predicate.
+    fn take<F: Fn(&ReadyCheck) -> bool> (&mut self, predicate: F) -> Vec<ReadyCheck> {
+        let mut taken = Vec::new();
+        let mut i = 0;
+        while i < self.checks.len() {
+            if predicate(&self.checks[i]) {
+                taken.push(self.checks.remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check () -> ReadyCheck {
+        ReadyCheck::construct("3v3", 0, &[1, 2, 3, 4, 5, 6], Duration::from_secs(60))
+    }
+
+    #[test]
+    fn completes_when_everyone_is_ready() {
+        let mut check = check();
+        for player in 1..=5 {
+            assert!(check.ready(player));
+        }
+        assert!(!check.is_complete());
+        assert!(check.ready(6));
+        assert!(check.is_complete());
+    }
+
+    #[test]
+    fn outsiders_and_repeats_are_ignored() {
+        let mut check = check();
+        assert!(!check.ready(7));
+        assert!(check.ready(1));
+        assert!(!check.ready(1));
+    }
+
+    #[test]
+    fn missing_players_keep_queue_order() {
+        let mut check = check();
+        check.ready(4);
+        check.ready(1);
+        check.ready(6);
+        assert_eq!(check.ready_players(), vec![1, 4, 6]);
+        assert_eq!(check.missing_players(), vec![2, 3, 5]);
+    }
+
+    #[test]
+    fn expires_at_deadline() {
+        let check = check();
+        assert!(!check.is_expired(SystemTime::now()));
+        assert!(check.is_expired(check.deadline));
+    }
+
+    #[test]
+    fn only_expired_checks_are_taken() {
+        let mut checks = ReadyChecks::construct(Duration::from_secs(60));
+        checks.add(check());
+        checks.add(ReadyCheck::construct("1v1", 0, &[7, 8], Duration::from_secs(0)));
+        let expired = checks.take_expired(SystemTime::now());
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].group, "1v1");
+        assert!(checks.find(1).is_some());
+    }
+}
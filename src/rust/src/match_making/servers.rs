@@ -0,0 +1,185 @@
+use rand:: {
+    distributions::Alphanumeric,
+    Rng
+};
+use serde:: {
+    Deserialize,
+    Serialize
+};
+use std::error::Error;
+
+/// length of the random g_password set on a server for each match.
+const PASSWORD_LENGTH: usize = 8;
+
+/// returns a random password for players to join an allocated server with.
+///
+/// # Example
+///
+/// ```
+/// let password = servers::generate_password();
+/// ```
+pub fn generate_password () -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(PASSWORD_LENGTH)
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Server structure for a game server matches can be played on
+///
+/// # Members
+///
+///     ```
+///     name: unique server name as defined by the configuration file
+///     host: hostname or ip address of the server
+///     port: port of the server
+///     rcon_password: rcon password of the server
+///     region: region the server is located in
+///     log: path of the server log that is followed to detect results, if any
+///     ```
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Server {
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub rcon_password: String,
+    pub region: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log: Option<String>
+}
+
+// Server implementation
+impl Server {
+    /// parses a server from its name and a `host port rcon_password region [log]`
+    /// specification as found in the servers section of the configuration file
+    /// used before the toml configuration.
+    /// the log is only given for servers running on the same machine as the bot.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let server = servers::Server::parse("eu1", "192.0.2.10 27960 secret eu").unwrap();
+    /// ```
+    pub fn parse (name: &str, specification: &str) -> Result<Self, Box<dyn Error>> {
+        let name = name.trim().to_lowercase();
+        let tokens: Vec<&str> = specification.split_whitespace().collect();
+        let (tokens, log) = match tokens.as_slice() {
+            [rest @ .., log] if rest.len() == 4 => (rest, Some(log.to_string())),
+            _ => (tokens.as_slice(), None)
+        };
+        match tokens {
+            [host, port, rcon_password, region] => Ok (
+                Self {
+                    port: port.parse().map_err(|_| format!("invalid port for server {}: {}", name, port))?,
+                    name,
+                    host: host.to_string(),
+                    rcon_password: rcon_password.to_string(),
+                    region: region.to_lowercase(),
+                    log
+                }
+            ),
+            _ => Err(format!("expected `host port rcon_password region [log]` for server {}", name).into())
+        }
+    }
+    /// returns the `host:port` address of the server.
+    pub fn address (&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+    /// returns the console command players enter to join the server with the
+    /// specified password.
+    pub fn connect_string (&self, password: &str) -> String {
+        format!("/connect {}; password {}", self.address(), password)
+    }
+}
+
+/// Servers structure for the pool of game servers matches are allocated to
+///
+/// # Members
+///
+///     ```
+///     servers: configured servers in the order they are allocated
+///     ```
+pub struct Servers {
+    servers: Vec<Server>
+}
+
+// Servers implementation
+impl Servers {
+    /// constructs the server pool from the configured servers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let servers = servers::Servers::construct(config.servers.clone());
+    /// ```
+    pub fn construct (servers: Vec<Server>) -> Self {
+        Self {
+            servers
+        }
+    }
+    /// returns every server of the pool in allocation order.
+    pub fn all (&self) -> &[Server] {
+        &self.servers
+    }
+    /// returns the server with the specified name, if it is configured.
+    pub fn find (&self, name: &str) -> Option<&Server> {
+        let name = name.trim().to_lowercase();
+        self.servers.iter().find(|s| s.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_server_specification() {
+        let server = Server::parse(" EU1 ", "192.0.2.10 27960 s3cr:et EU").unwrap();
+        assert_eq!(server.name, "eu1");
+        assert_eq!(server.address(), "192.0.2.10:27960");
+        assert_eq!(server.rcon_password, "s3cr:et");
+        assert_eq!(server.region, "eu");
+        assert_eq!(server.log, None);
+    }
+
+    #[test]
+    fn parses_optional_log_path() {
+        let server = Server::parse("eu1", "192.0.2.10 27960 secret eu /home/et/etpro/etconsole.log").unwrap();
+        assert_eq!(server.region, "eu");
+        assert_eq!(server.log.as_deref(), Some("/home/et/etpro/etconsole.log"));
+    }
+
+    #[test]
+    fn rejects_incomplete_specification() {
+        assert!(Server::parse("eu1", "192.0.2.10 27960 secret").is_err());
+        assert!(Server::parse("eu1", "192.0.2.10 port secret eu").is_err());
+        assert!(Server::parse("eu1", "192.0.2.10 27960 secret eu log extra").is_err());
+    }
+
+    #[test]
+    fn formats_connect_string() {
+        let server = Server::parse("eu1", "192.0.2.10 27960 secret eu").unwrap();
+        assert_eq!(server.connect_string("abc"), "/connect 192.0.2.10:27960; password abc");
+    }
+
+    #[test]
+    fn finds_servers_by_name() {
+        let servers = Servers::construct(vec![
+            Server::parse("eu1", "192.0.2.10 27960 secret eu").unwrap(),
+            Server::parse("na1", "192.0.2.20 27960 secret na").unwrap()
+        ]);
+        assert_eq!(servers.find("NA1").unwrap().host, "192.0.2.20");
+        assert!(servers.find("eu2").is_none());
+        assert_eq!(servers.all().len(), 2);
+    }
+
+    #[test]
+    fn passwords_are_random_alphanumerics() {
+        let password = generate_password();
+        assert_eq!(password.len(), PASSWORD_LENGTH);
+        assert!(password.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_ne!(password, generate_password());
+    }
+}
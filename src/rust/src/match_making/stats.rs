@@ -0,0 +1,198 @@
+use crate::match_making::links::Identity;
+use et_mm_bot::et::log:: {
+    Clients,
+    Event
+};
+use std::collections::HashMap;
+
+/// WeaponTotals structure for the accuracy of a player with a single weapon
+///
+/// # Members
+///
+///     ```
+///     hits: shots that hit an enemy
+///     shots: shots fired
+///     kills: kills made with the weapon
+///     headshots: hits to the head
+///     ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WeaponTotals {
+    pub hits: u32,
+    pub shots: u32,
+    pub kills: u32,
+    pub headshots: u32
+}
+
+/// PlayerStats structure for the statistics of a player over a match
+///
+/// # Members
+///
+///     ```
+///     kills: enemies killed
+///     deaths: times killed, including by themselves or the world
+///     gibs: bodies gibbed
+///     revives: teammates revived
+///     damage_given: damage dealt to enemies
+///     damage_received: damage taken from enemies
+///     objectives: dynamite planted or defused
+///     weapons: accuracy with each weapon, by weapon index
+///     ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlayerStats {
+    pub kills: u32,
+    pub deaths: u32,
+    pub gibs: u32,
+    pub revives: u32,
+    pub damage_given: u32,
+    pub damage_received: u32,
+    pub objectives: u32,
+    pub weapons: HashMap<u32, WeaponTotals>
+}
+
+/// MatchStats structure for the statistics of every player of a match, as
+/// read from the log of the server it is played on
+///
+/// # Members
+///
+///     ```
+///     players: statistics of each player by in-game identity
+///     ```
+#[derive(Default)]
+pub struct MatchStats {
+    players: HashMap<Identity, PlayerStats>
+}
+
+// MatchStats implementation
+impl MatchStats {
+    /// constructs empty match statistics.
+    pub fn construct () -> Self {
+        Self::default()
+    }
+    /// returns the statistics of every player that has any.
+    pub fn players (&self) -> &HashMap<Identity, PlayerStats> {
+        &self.players
+    }
+    /// adds a log event to the statistics of the players it involves. players
+    /// are identified by the clients connected to the server at the time, and
+    /// slots without a client, such as the world, are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// stats.record(&Event::Kill { killer: 0, victim: 1, means: "MOD_MP40".to_string() }, &clients);
+    /// ```
+    pub fn record (&mut self, event: &Event, clients: &Clients) {
+        match event {
+            Event::Kill { killer, victim, .. } => {
+                if killer != victim {
+                    if let Some(p) = self.player(*killer, clients) {
+                        p.kills += 1;
+                    }
+                }
+                if let Some(p) = self.player(*victim, clients) {
+                    p.deaths += 1;
+                }
+            },
+            Event::WeaponStats { slot, weapons, damage_given, damage_received, gibs, .. } => {
+                if let Some(p) = self.player(*slot, clients) {
+                    p.damage_given += damage_given;
+                    p.damage_received += damage_received;
+                    p.gibs += gibs;
+                    for weapon in weapons.iter() {
+                        let totals = p.weapons.entry(weapon.weapon).or_default();
+                        totals.hits += weapon.hits;
+                        totals.shots += weapon.shots;
+                        totals.kills += weapon.kills;
+                        totals.headshots += weapon.headshots;
+                    }
+                }
+            },
+            Event::Revive { medic, .. } => {
+                if let Some(p) = self.player(*medic, clients) {
+                    p.revives += 1;
+                }
+            },
+            Event::Objective { slot, .. } => {
+                if let Some(p) = self.player(*slot, clients) {
+                    p.objectives += 1;
+                }
+            },
+            _ => ()
+        };
+    }
+    /// returns the statistics of the client in the specified slot.
+    fn player (&mut self, slot: u32, clients: &Clients) -> Option<&mut PlayerStats> {
+        let identity = Identity::from_userinfo(clients.get(slot)?)?;
+        Some(self.players.entry(identity).or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use et_mm_bot::et::log:: {
+        self,
+        WeaponStat
+    };
+
+    fn clients () -> Clients {
+        let mut clients = Clients::construct();
+        for line in ["ClientConnect: 0", "ClientUserinfoChanged: 0 n\\red\\cl_guid\\AAAA", "ClientConnect: 1", "ClientUserinfoChanged: 1 n\\blue"].iter() {
+            clients.update(&log::parse_line(line).unwrap());
+        }
+        clients
+    }
+
+    fn red () -> Identity {
+        Identity::Guid("AAAA".to_string())
+    }
+
+    fn blue () -> Identity {
+        Identity::Name("blue".to_string())
+    }
+
+    #[test]
+    fn kills_and_deaths_are_counted() {
+        let clients = clients();
+        let mut stats = MatchStats::construct();
+        stats.record(&Event::Kill { killer: 0, victim: 1, means: "MOD_MP40".to_string() }, &clients);
+        stats.record(&Event::Kill { killer: 1, victim: 1, means: "MOD_GRENADE".to_string() }, &clients);
+        stats.record(&Event::Kill { killer: 1022, victim: 0, means: "MOD_FALLING".to_string() }, &clients);
+        assert_eq!(stats.players()[&red()].kills, 1);
+        assert_eq!(stats.players()[&red()].deaths, 1);
+        assert_eq!(stats.players()[&blue()].kills, 0);
+        assert_eq!(stats.players()[&blue()].deaths, 2);
+    }
+
+    #[test]
+    fn weapon_stats_add_up_over_rounds() {
+        let clients = clients();
+        let mut stats = MatchStats::construct();
+        let round = Event::WeaponStats {
+            slot: 0,
+            weapons: vec![WeaponStat { weapon: 6, hits: 4, shots: 20, kills: 1, deaths: 0, headshots: 2 }],
+            damage_given: 350,
+            damage_received: 120,
+            team_damage: 0,
+            gibs: 3
+        };
+        stats.record(&round, &clients);
+        stats.record(&round, &clients);
+        let red = &stats.players()[&red()];
+        assert_eq!(red.damage_given, 700);
+        assert_eq!(red.gibs, 6);
+        assert_eq!(red.weapons[&6], WeaponTotals { hits: 8, shots: 40, kills: 2, headshots: 4 });
+    }
+
+    #[test]
+    fn revives_and_objectives_are_counted() {
+        let clients = clients();
+        let mut stats = MatchStats::construct();
+        stats.record(&Event::Revive { medic: 1, revived: 0 }, &clients);
+        stats.record(&Event::Objective { slot: 1, action: "Dynamite_Plant".to_string() }, &clients);
+        stats.record(&Event::Objective { slot: 7, action: "Dynamite_Plant".to_string() }, &clients);
+        assert_eq!(stats.players()[&blue()].revives, 1);
+        assert_eq!(stats.players()[&blue()].objectives, 1);
+        assert_eq!(stats.players().len(), 1);
+    }
+}
@@ -0,0 +1,164 @@
+use et_mm_bot::et::log:: {
+    self,
+    Clients,
+    Event,
+    LogTail,
+    Team,
+    WeaponStat
+};
+use std:: {
+    fs:: {
+        self,
+        OpenOptions
+    },
+    io::prelude::*,
+    path::PathBuf
+};
+
+const FIXTURE: &str = include_str!("fixtures/games.log");
+
+/// returns the events of every line of the fixture log the parser recognises.
+fn fixture_events () -> Vec<Event> {
+    FIXTURE.lines().filter_map(log::parse_line).collect()
+}
+
+/// returns a path in the temporary directory that is unique to a test.
+fn temporary_log (name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("et-mm-bot-{}-{}.log", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn recognises_fixture_events() {
+    let events = fixture_events();
+    assert_eq!(events.len(), 15);
+    assert_eq!(events[0], Event::InitGame(et_mm_bot::et::query::parse_infostring(
+        "\\g_gametype\\3\\gamename\\etpro\\mapname\\supply\\sv_hostname\\^1ET^7-^4MM ^7pug #1\\sv_maxclients\\12\\g_needpass\\1"
+    )));
+    assert_eq!(events[1], Event::ClientConnect(0));
+    assert_eq!(events[14], Event::ShutdownGame);
+}
+
+#[test]
+fn parses_userinfo_changes() {
+    match &fixture_events()[2] {
+        Event::ClientUserinfoChanged { slot, info } => {
+            assert_eq!(*slot, 0);
+            assert_eq!(info["n"], "^1red^7name");
+            assert_eq!(info["t"], "1");
+            assert_eq!(info["dn"], "");
+        },
+        e => panic!("expected userinfo change, got {:?}", e)
+    };
+}
+
+#[test]
+fn parses_kills() {
+    let events = fixture_events();
+    assert_eq!(events[5], Event::Kill { killer: 0, victim: 1, means: "MOD_MP40".to_string() });
+    assert_eq!(events[6], Event::Kill { killer: 1, victim: 1, means: "MOD_GRENADE_LAUNCHER".to_string() });
+    assert_eq!(events[10], Event::ClientDisconnect(1));
+}
+
+#[test]
+fn parses_chat() {
+    assert_eq!(fixture_events()[7], Event::Say { name: "^1red^7name".to_string(), text: "gg".to_string() });
+    assert_eq!(
+        log::parse_line("  3:00 sayteam: blue name: link-abc123 please"),
+        Some(Event::Say { name: "blue name".to_string(), text: "link-abc123 please".to_string() })
+    );
+}
+
+#[test]
+fn tracks_connected_clients() {
+    let mut clients = Clients::construct();
+    for event in fixture_events().iter() {
+        clients.update(event);
+    }
+    let (slot, info) = clients.find_by_name("^1red^7name").unwrap();
+    assert_eq!(slot, 0);
+    assert_eq!(info["t"], "1");
+    assert!(clients.find_by_name("blue name").is_none());
+    assert!(clients.get(1).is_none());
+}
+
+#[test]
+fn parses_revives_and_objectives() {
+    let events = fixture_events();
+    assert_eq!(events[8], Event::Revive { medic: 1, revived: 0 });
+    assert_eq!(events[9], Event::Objective { slot: 0, action: "Dynamite_Plant".to_string() });
+    assert_eq!(log::parse_line("  5:00 Medic_Revive: 1"), None);
+}
+
+#[test]
+fn parses_round_winner() {
+    assert_eq!(fixture_events()[11], Event::Exit { reason: "Axis team wins".to_string(), winner: Some(Team::Axis) });
+    assert_eq!(
+        log::parse_line("12:00 Exit: Timelimit hit."),
+        Some(Event::Exit { reason: "Timelimit hit.".to_string(), winner: None })
+    );
+}
+
+#[test]
+fn parses_weapon_stats() {
+    let events = fixture_events();
+    assert_eq!(events[12], Event::WeaponStats {
+        slot: 0,
+        weapons: vec![
+            WeaponStat { weapon: 6, hits: 4, shots: 20, kills: 1, deaths: 0, headshots: 2 },
+            WeaponStat { weapon: 10, hits: 1, shots: 3, kills: 0, deaths: 1, headshots: 0 }
+        ],
+        damage_given: 350,
+        damage_received: 120,
+        team_damage: 25,
+        gibs: 3
+    });
+    assert_eq!(events[13], Event::WeaponStats {
+        slot: 1,
+        weapons: Vec::new(),
+        damage_given: 0,
+        damage_received: 0,
+        team_damage: 0,
+        gibs: 0
+    });
+}
+
+#[test]
+fn ignores_malformed_lines() {
+    assert_eq!(log::parse_line(""), None);
+    assert_eq!(log::parse_line("  1:00 ClientConnect: x"), None);
+    assert_eq!(log::parse_line("  1:00 Kill: 0 1: missing means"), None);
+    assert_eq!(log::parse_line("  1:00 WeaponStats: 0 1 1 4 20"), None);
+}
+
+#[test]
+fn parses_lines_without_server_time() {
+    assert_eq!(log::parse_line("ClientConnect: 3"), Some(Event::ClientConnect(3)));
+}
+
+#[test]
+fn tails_appended_lines() {
+    let path = temporary_log("tail");
+    fs::write(&path, "  0:00 ClientConnect: 0\n").unwrap();
+    let mut tail = LogTail::construct(path.to_str().unwrap());
+    assert!(tail.read_lines().unwrap().is_empty());
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(b"  0:01 ClientConnect: 1\n  0:02 Exit: Allies").unwrap();
+    assert_eq!(tail.read_lines().unwrap(), vec!["  0:01 ClientConnect: 1".to_string()]);
+    file.write_all(b" team wins\n").unwrap();
+    assert_eq!(tail.read_lines().unwrap(), vec!["  0:02 Exit: Allies team wins".to_string()]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn tails_rotated_and_missing_files() {
+    let path = temporary_log("rotate");
+    let mut tail = LogTail::construct(path.to_str().unwrap());
+    assert!(tail.read_lines().unwrap().is_empty());
+    fs::write(&path, "  0:00 ClientConnect: 0\n  0:00 ClientConnect: 1\n").unwrap();
+    assert_eq!(tail.read_lines().unwrap().len(), 2);
+    fs::write(&path, "  0:00 ShutdownGame:\n").unwrap();
+    assert_eq!(tail.read_lines().unwrap(), vec!["  0:00 ShutdownGame:".to_string()]);
+    fs::remove_file(&path).unwrap();
+}
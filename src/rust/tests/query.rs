@@ -0,0 +1,122 @@
+use et_mm_bot::et::query:: {
+    self,
+    Player,
+    QueryError,
+    ServerInfo,
+    ServerStatus
+};
+use std:: {
+    net::UdpSocket,
+    thread,
+    time::Duration
+};
+
+const STATUS: &str = "statusResponse\n\
+    \\g_needpass\\1\\gamename\\etpro\\mapname\\goldrush\\sv_maxclients\\12\\sv_hostname\\^1ET^7-^4MM ^7pug #1\\g_gametype\\3\n\
+    12 48 \"^1red^7name\"\n\
+    0 999 \"connecting\"\n\
+    -3 62 \"^^00spaced name\"\n";
+
+/// starts a stand-in ET server on a local port that answers a single query
+/// with the specified response.
+fn stand_in (expected: &'static str, response: &'static str) -> String {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let address = socket.local_addr().unwrap().to_string();
+    thread::spawn(move || {
+        let mut buffer = [0u8; 2048];
+        let (length, client) = socket.recv_from(&mut buffer).unwrap();
+        assert_eq!(&buffer[4..length], expected.as_bytes());
+        let mut packet = b"\xff\xff\xff\xff".to_vec();
+        packet.extend_from_slice(response.as_bytes());
+        socket.send_to(&packet, client).unwrap();
+    });
+    address
+}
+
+#[test]
+fn strips_color_codes() {
+    assert_eq!(query::strip_colors("^1ET^7-^4MM"), "ET-MM");
+    assert_eq!(query::strip_colors("trailing^"), "trailing^");
+}
+
+#[test]
+fn keeps_caret_before_caret() {
+    assert_eq!(query::strip_colors("^^00spaced name"), "^0spaced name");
+    assert_eq!(query::strip_colors("a^^^1b"), "a^^b");
+    assert_eq!(query::strip_colors("^^"), "^^");
+}
+
+#[test]
+fn parses_infostring() {
+    let info = query::parse_infostring("\\sv_hostname\\pug\\empty\\\\mapname\\radar");
+    assert_eq!(info["sv_hostname"], "pug");
+    assert_eq!(info["empty"], "");
+    assert_eq!(info["mapname"], "radar");
+}
+
+#[test]
+fn parses_player_lines() {
+    let player = Player::parse("12 48 \"^1red^7name\"").unwrap();
+    assert_eq!(player, Player { score: 12, ping: 48, name: "^1red^7name".to_string() });
+    assert_eq!(player.clean_name(), "redname");
+    assert!(Player::parse("garbage").is_err());
+}
+
+#[test]
+fn parses_status_response() {
+    let status = ServerStatus::parse(STATUS).unwrap();
+    assert_eq!(query::strip_colors(&status.hostname), "ET-MM pug #1");
+    assert_eq!(status.map, "goldrush");
+    assert_eq!(status.gametype, 3);
+    assert_eq!(query::gametype_name(status.gametype), "stopwatch");
+    assert_eq!(status.max_clients, 12);
+    assert!(status.needs_password);
+    assert_eq!(status.gamename, "etpro");
+    assert_eq!(status.players.len(), 3);
+    assert_eq!(status.players[2].score, -3);
+    assert!(!status.is_empty());
+}
+
+#[test]
+fn parses_empty_server() {
+    let status = ServerStatus::parse("statusResponse\n\\mapname\\supply\\g_needpass\\0\n").unwrap();
+    assert!(status.is_empty());
+    assert!(!status.needs_password);
+}
+
+#[test]
+fn rejects_other_responses() {
+    match ServerStatus::parse("infoResponse\n\\mapname\\supply") {
+        Err(QueryError::InvalidResponse(_)) => (),
+        r => panic!("expected invalid response, got {:?}", r)
+    };
+}
+
+#[test]
+fn queries_status() {
+    let address = stand_in("getstatus", STATUS);
+    let status = query::status(&address, Duration::from_millis(500)).unwrap();
+    assert_eq!(status.map, "goldrush");
+    assert_eq!(status.players[0].clean_name(), "redname");
+}
+
+#[test]
+fn queries_info() {
+    let address = stand_in(
+        "getinfo etmm",
+        "infoResponse\n\\challenge\\etmm\\clients\\4\\sv_maxclients\\12\\hostname\\pug\\mapname\\radar\\gametype\\2\\needpass\\0\\gamename\\etmain"
+    );
+    let info = query::info(&address, Duration::from_millis(500)).unwrap();
+    assert_eq!(info.clients, 4);
+    assert_eq!(info.max_clients, 12);
+    assert_eq!(info.map, "radar");
+    assert_eq!(info.gamename, "etmain");
+    assert!(!info.needs_password);
+}
+
+#[test]
+fn parses_info_response() {
+    let info = ServerInfo::parse("infoResponse\n\\hostname\\x\\clients\\0").unwrap();
+    assert_eq!(info.clients, 0);
+    assert_eq!(info.hostname, "x");
+}
@@ -0,0 +1,96 @@
+use et_mm_bot::et::rcon:: {
+    Rcon,
+    RconError
+};
+use std:: {
+    net::UdpSocket,
+    thread,
+    time::Duration
+};
+
+/// starts a stand-in ET server on a local port that answers a single rcon
+/// request with the packets produced by the responder.
+fn stand_in<F> (responder: F) -> String
+where
+    F: FnOnce(&str) -> Vec<Vec<u8>> + Send + 'static
+{
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let address = socket.local_addr().unwrap().to_string();
+    thread::spawn(move || {
+        let mut buffer = [0u8; 2048];
+        let (length, client) = socket.recv_from(&mut buffer).unwrap();
+        assert_eq!(&buffer[..4], b"\xff\xff\xff\xff");
+        let request = String::from_utf8_lossy(&buffer[4..length]).to_string();
+        for packet in responder(&request) {
+            socket.send_to(&packet, client).unwrap();
+        }
+    });
+    address
+}
+
+/// builds a printed response packet as sent by an ET server.
+fn print (text: &str) -> Vec<u8> {
+    let mut packet = b"\xff\xff\xff\xffprint\n".to_vec();
+    packet.extend_from_slice(text.as_bytes());
+    packet
+}
+
+fn rcon (address: &str, password: &str) -> Rcon {
+    Rcon::construct(address, password, Duration::from_millis(500)).unwrap()
+}
+
+#[test]
+fn sends_password_and_command() {
+    let address = stand_in(|request| {
+        assert_eq!(request, "rcon secret map supply");
+        vec![print("map changed\n")]
+    });
+    assert_eq!(rcon(&address, "secret").send("map supply").unwrap(), "map changed\n");
+}
+
+#[test]
+fn reassembles_multi_packet_responses() {
+    let address = stand_in(|_| vec![
+        print("map: supply\nnum score ping name\n"),
+        print("  0    10   50 ^1player one\n"),
+        print("  1     5   70 ^2player two\n")
+    ]);
+    let response = rcon(&address, "secret").send("status").unwrap();
+    assert_eq!(response, "map: supply\nnum score ping name\n  0    10   50 ^1player one\n  1     5   70 ^2player two\n");
+}
+
+#[test]
+fn reports_bad_password() {
+    let address = stand_in(|_| vec![print("Bad rconpassword.\n")]);
+    match rcon(&address, "wrong").send("status") {
+        Err(RconError::BadPassword) => (),
+        r => panic!("expected bad password, got {:?}", r)
+    };
+}
+
+#[test]
+fn reports_missing_password() {
+    let address = stand_in(|_| vec![print("No rconpassword set on the server.\n")]);
+    match rcon(&address, "secret").send("status") {
+        Err(RconError::NoPassword) => (),
+        r => panic!("expected no password, got {:?}", r)
+    };
+}
+
+#[test]
+fn times_out_when_server_is_silent() {
+    let address = stand_in(|_| Vec::new());
+    match rcon(&address, "secret").send("status") {
+        Err(RconError::Timeout) => (),
+        r => panic!("expected timeout, got {:?}", r)
+    };
+}
+
+#[test]
+fn rejects_unexpected_responses() {
+    let address = stand_in(|_| vec![b"\xff\xff\xff\xffstatusResponse\n".to_vec()]);
+    match rcon(&address, "secret").send("status") {
+        Err(RconError::InvalidResponse(_)) => (),
+        r => panic!("expected invalid response, got {:?}", r)
+    };
+}